@@ -0,0 +1,135 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::solution::DayReport;
+use crate::{data_path, load_json};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// Path of the accepted answers that override the ones found in puzzle files.
+pub fn answers_path(year: u16) -> String {
+    format!("data/{}/answers.json", year)
+}
+
+/// Path of the known answers for the input of a profile, next to its input files.
+pub fn profile_answers_path(year: u16, profile: &str) -> String {
+    format!("data/{}/inputs/{}/answers.json", year, profile)
+}
+
+const ANSWER_PREFIX: &str = "Your puzzle answer was `";
+
+/// Known answers for part one and part two of a day.
+pub type Answers = [Option<String>; 2];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Regressed,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "✓",
+            Verdict::Regressed => "✗",
+            Verdict::Unknown => "?",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Regressed => "regressed",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+/// Extracts accepted answers from the "Your puzzle answer was `...`." lines of a puzzle file.
+pub fn parse_puzzle_answers(markdown: &str) -> Answers {
+    let mut answers = markdown.lines().filter_map(|line| {
+        let rest = line.trim().strip_prefix(ANSWER_PREFIX)?;
+        let (answer, _) = rest.split_once('`')?;
+        Some(answer.to_string())
+    });
+    [answers.next(), answers.next()]
+}
+
+type AnswerStore = BTreeMap<u8, Answers>;
+
+fn read_store(path: &Path, day: u8) -> io::Result<Answers> {
+    let store: AnswerStore = load_json(path)?.unwrap_or_default();
+    Ok(store.get(&day).cloned().unwrap_or_default())
+}
+
+/// Looks up the accepted answers for a day. Entries in `data/<year>/answers.json` take precedence
+/// over answers found in `data/<year>/puzzles/NN.md`. Puzzle files only hold the answers of the
+/// session's own account, so a profile's answers come from its own answer store.
+pub fn known_answers(year: u16, day: u8, profile: Option<&str>) -> io::Result<Answers> {
+    if let Some(profile) = profile {
+        return read_store(Path::new(&profile_answers_path(year, profile)), day);
+    }
+
+    let stored = read_store(Path::new(&answers_path(year)), day)?;
+    let puzzle = fs::read_to_string(data_path(year, "puzzles", day, "md"))
+        .map(|md| parse_puzzle_answers(&md))
+        .unwrap_or_default();
+
+    let [one, two] = stored;
+    let [puzzle_one, puzzle_two] = puzzle;
+    Ok([one.or(puzzle_one), two.or(puzzle_two)])
+}
+
+pub fn verify(known: Option<&str>, answer: Option<&str>) -> Verdict {
+    match (known, answer) {
+        (None, _) => Verdict::Unknown,
+        (Some(known), Some(answer)) if known == answer => Verdict::Correct,
+        (Some(_), _) => Verdict::Regressed,
+    }
+}
+
+/// Checks every part of a report against the known answers.
+pub fn check(report: &DayReport, known: &Answers) -> Vec<Verdict> {
+    report
+        .parts
+        .iter()
+        .map(|part| {
+            let known = known[part.part as usize - 1].as_deref();
+            verify(known, part.answer.as_deref())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_puzzle_answers() {
+        let markdown = "Find the Elf.\n\nYour puzzle answer was `72070`.\n\n\\--- Part Two ---\n\nYour puzzle answer was `RLFNRTNFB`.\n";
+        assert_eq!(
+            parse_puzzle_answers(markdown),
+            [Some("72070".to_string()), Some("RLFNRTNFB".to_string())]
+        );
+        assert_eq!(
+            parse_puzzle_answers("Your puzzle answer was `8392`."),
+            [Some("8392".to_string()), None]
+        );
+        assert_eq!(parse_puzzle_answers("no answers yet"), [None, None]);
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(verify(Some("42"), Some("42")), Verdict::Correct);
+        assert_eq!(verify(Some("42"), Some("41")), Verdict::Regressed);
+        assert_eq!(verify(Some("42"), None), Verdict::Regressed);
+        assert_eq!(verify(None, Some("42")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn test_store_format() {
+        let store: AnswerStore = serde_json::from_str(r#"{ "5": ["RLFNRTNFB", null] }"#).unwrap();
+        assert_eq!(store.get(&5), Some(&[Some("RLFNRTNFB".to_string()), None]));
+    }
+}
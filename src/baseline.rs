@@ -0,0 +1,250 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::bench::{BenchReport, Stats};
+use crate::{load_json, save_json};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io, path::Path, time::Duration};
+
+/// Path of the baseline of the default input, or of a profile's inputs next to them.
+pub fn baseline_path(year: u16, profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!("data/{}/inputs/{}/timings.json", year, profile),
+        None => format!("data/{}/timings.json", year),
+    }
+}
+
+/// Relative change below which a timing difference is not reported.
+const THRESHOLD: f64 = 0.1;
+
+/// Whether this is an optimized build. Timings are only comparable within the same kind of build.
+pub const OPTIMIZED: bool = !cfg!(debug_assertions);
+
+fn default_optimized() -> bool {
+    true
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub median_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl From<&Stats> for Timing {
+    fn from(stats: &Stats) -> Self {
+        Self {
+            median_ns: stats.median.as_nanos() as u64,
+            stddev_ns: stats.stddev.as_nanos() as u64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayTimings {
+    pub parse: Timing,
+    pub parts: [Timing; 2],
+}
+
+impl DayTimings {
+    pub fn total(&self) -> Duration {
+        let ns = self.parse.median_ns + self.parts.iter().map(|p| p.median_ns).sum::<u64>();
+        Duration::from_nanos(ns)
+    }
+}
+
+impl From<&BenchReport> for DayTimings {
+    fn from(report: &BenchReport) -> Self {
+        Self {
+            parse: (&report.parse).into(),
+            parts: [(&report.parts[0]).into(), (&report.parts[1]).into()],
+        }
+    }
+}
+
+/// Benchmark timings of every day of a year, as persisted in `data/<year>/timings.json` or, for a
+/// profile, next to its inputs.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Whether the timings were measured with an optimized build. Baselines written before this
+    /// was recorded came from `cargo time`, which always builds with `--release`.
+    #[serde(default = "default_optimized")]
+    pub optimized: bool,
+    pub days: BTreeMap<u8, DayTimings>,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            optimized: OPTIMIZED,
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+impl Baseline {
+    /// Loads the baseline from `path`. A missing file yields an empty baseline.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(load_json(path)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_json(path, self)
+    }
+
+    /// Whether the timings were measured with the same kind of build as the running one.
+    pub fn is_comparable(&self) -> bool {
+        self.optimized == OPTIMIZED
+    }
+
+    pub fn get(&self, day: u8) -> Option<&DayTimings> {
+        self.days.get(&day)
+    }
+
+    pub fn record(&mut self, day: u8, report: &BenchReport) {
+        self.days.insert(day, report.into());
+    }
+}
+
+/// The summed timings of the days that have a baseline, so that days without one do not show up
+/// as a regression of the total.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TotalDelta {
+    pub current: Duration,
+    pub baseline: Duration,
+    pub days: usize,
+}
+
+impl TotalDelta {
+    pub fn add(&mut self, current: Duration, baseline: Duration) {
+        self.current += current;
+        self.baseline += baseline;
+        self.days += 1;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    Slower(f64),
+    Faster(f64),
+    Unchanged,
+}
+
+/// Compares a new measurement against its baseline. Changes are only reported when they exceed
+/// both the relative threshold and the noise (two standard deviations) of either measurement.
+pub fn compare(baseline: &Timing, current: &Timing) -> Change {
+    if baseline.median_ns == 0 {
+        return Change::Unchanged;
+    }
+
+    let old = baseline.median_ns as f64;
+    let new = current.median_ns as f64;
+    let relative = (new - old) / old;
+    let noise = 2.0 * baseline.stddev_ns.max(current.stddev_ns) as f64;
+
+    if relative.abs() < THRESHOLD || (new - old).abs() <= noise {
+        Change::Unchanged
+    } else if relative > 0.0 {
+        Change::Slower(relative * 100.0)
+    } else {
+        Change::Faster(-relative * 100.0)
+    }
+}
+
+/// Formats the signed difference between two durations, e.g. `+1.20ms`.
+pub fn format_delta(baseline: Duration, current: Duration) -> String {
+    if current >= baseline {
+        format!("+{:.2?}", current - baseline)
+    } else {
+        format!("-{:.2?}", baseline - current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(median_ns: u64, stddev_ns: u64) -> Timing {
+        Timing {
+            median_ns,
+            stddev_ns,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(
+            compare(&timing(1000, 10), &timing(1050, 10)),
+            Change::Unchanged
+        );
+        assert_eq!(
+            compare(&timing(1000, 10), &timing(1500, 10)),
+            Change::Slower(50.0)
+        );
+        assert_eq!(
+            compare(&timing(1000, 10), &timing(750, 10)),
+            Change::Faster(25.0)
+        );
+        // within noise.
+        assert_eq!(
+            compare(&timing(1000, 400), &timing(1500, 10)),
+            Change::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_baseline_path() {
+        assert_eq!(baseline_path(2022, None), "data/2022/timings.json");
+        assert_eq!(
+            baseline_path(2022, Some("alice")),
+            "data/2022/inputs/alice/timings.json"
+        );
+    }
+
+    #[test]
+    fn test_format_delta() {
+        assert_eq!(
+            format_delta(Duration::from_millis(2), Duration::from_millis(3)),
+            "+1.00ms"
+        );
+        assert_eq!(
+            format_delta(Duration::from_millis(3), Duration::from_micros(2500)),
+            "-500.00µs"
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut baseline = Baseline::default();
+        baseline.days.insert(
+            19,
+            DayTimings {
+                parse: timing(1, 2),
+                parts: [timing(3, 4), timing(5, 6)],
+            },
+        );
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+        assert_eq!(baseline.get(19).unwrap().total(), Duration::from_nanos(9));
+
+        // baselines from before builds were recorded are optimized.
+        let old: Baseline = serde_json::from_str("{\"days\": {}}").unwrap();
+        assert!(old.optimized);
+    }
+
+    #[test]
+    fn test_total_delta() {
+        let ns = Duration::from_nanos;
+        let mut delta = TotalDelta::default();
+        delta.add(ns(150), ns(100));
+        delta.add(ns(50), ns(100));
+        assert_eq!(
+            delta,
+            TotalDelta {
+                current: Duration::from_nanos(200),
+                baseline: Duration::from_nanos(200),
+                days: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,185 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::baseline::{self, Change, DayTimings, Timing};
+use crate::solution::Solution;
+use crate::{ANSI_BOLD, ANSI_RESET};
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Controls how many times each stage is run when benchmarking.
+pub struct BenchConfig {
+    /// Time spent running a stage before samples are recorded.
+    pub warmup: Duration,
+    /// Time budget for sampling a stage, after warmup.
+    pub budget: Duration,
+    pub min_samples: usize,
+    pub max_samples: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot compute stats without samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / n as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Self {
+            iterations: n,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+pub struct BenchReport {
+    pub parse: Stats,
+    pub parts: [Stats; 2],
+}
+
+fn sample<T>(config: &BenchConfig, mut func: impl FnMut() -> T) -> Stats {
+    let warmup = Instant::now();
+    while warmup.elapsed() < config.warmup {
+        black_box(func());
+    }
+
+    let mut samples = Vec::new();
+    let budget = Instant::now();
+    while samples.len() < config.max_samples
+        && (samples.len() < config.min_samples || budget.elapsed() < config.budget)
+    {
+        let timer = Instant::now();
+        let result = func();
+        samples.push(timer.elapsed());
+        // drop the result outside of the timed section.
+        black_box(result);
+    }
+
+    Stats::from_samples(&samples)
+}
+
+pub fn bench<S: Solution>(input: &str, config: &BenchConfig) -> BenchReport {
+    let parse = sample(config, || S::parse(black_box(input)));
+    let parsed = S::parse(input);
+    BenchReport {
+        parse,
+        parts: [
+            sample(config, || S::part_one(black_box(&parsed))),
+            sample(config, || S::part_two(black_box(&parsed))),
+        ],
+    }
+}
+
+fn format_change(baseline: Option<&Timing>, stats: &Stats) -> String {
+    match baseline.map(|b| baseline::compare(b, &stats.into())) {
+        Some(Change::Slower(pct)) => format!(" · {}▲ {:.1}% slower{}", ANSI_BOLD, pct, ANSI_RESET),
+        Some(Change::Faster(pct)) => format!(" · {}▼ {:.1}% faster{}", ANSI_BOLD, pct, ANSI_RESET),
+        Some(Change::Unchanged) | None => String::new(),
+    }
+}
+
+fn print_stats(label: &str, stats: &Stats, baseline: Option<&Timing>) {
+    println!(
+        "🎄 {}{}{}: median {:.2?} · mean {:.2?} ± {:.2?} · min {:.2?} ({} iterations){}",
+        ANSI_BOLD,
+        label,
+        ANSI_RESET,
+        stats.median,
+        stats.mean,
+        stats.stddev,
+        stats.min,
+        stats.iterations,
+        format_change(baseline, stats)
+    );
+}
+
+/// Prints the benchmark results, flagging stages that changed meaningfully against `baseline`.
+pub fn print_bench_report(report: &BenchReport, baseline: Option<&DayTimings>) {
+    print_stats("Parser", &report.parse, baseline.map(|b| &b.parse));
+    for (i, stats) in report.parts.iter().enumerate() {
+        print_stats(
+            &format!("Part {}", i + 1),
+            stats,
+            baseline.map(|b| &b.parts[i]),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(6), ms(4)]);
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(4));
+        assert_eq!(stats.mean, ms(4));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+
+        let stats = Stats::from_samples(&[ms(3), ms(1), ms(2)]);
+        assert_eq!(stats.median, ms(2));
+        assert_eq!(stats.min, ms(1));
+    }
+
+    #[test]
+    fn test_sample_respects_limits() {
+        let config = BenchConfig {
+            warmup: Duration::ZERO,
+            budget: Duration::ZERO,
+            min_samples: 3,
+            max_samples: 5,
+        };
+        assert_eq!(sample(&config, || 1).iterations, 3);
+
+        let config = BenchConfig {
+            budget: Duration::from_secs(60),
+            ..config
+        };
+        assert_eq!(sample(&config, || 1).iterations, 5);
+    }
+}
@@ -1,3 +1,4 @@
+use advent_of_code::Solution;
 use itertools::Itertools;
 
 fn parse(input: &str) -> Vec<u32> {
@@ -21,11 +22,28 @@ pub fn part_two(calories: &[u32]) -> Option<u32> {
     )
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<u32>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne> {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo> {
+        part_two(parsed)
+    }
+}
+
 fn main() {
-    let input = advent_of_code::read_file("inputs", 1);
-    let parsed = advent_of_code::run_parser(parse, &input);
-    advent_of_code::solve!(1, part_one, &parsed);
-    advent_of_code::solve!(2, part_two, &parsed);
+    advent_of_code::solve_day::<Puzzle>(2022, 1);
 }
 
 #[cfg(test)]
@@ -34,14 +52,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 1);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 1);
         let calories = parse(&input);
         assert_eq!(part_one(&calories), Some(24000));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 1);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 1);
         let calories = parse(&input);
         assert_eq!(part_two(&calories), Some(45000));
     }
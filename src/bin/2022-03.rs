@@ -1,3 +1,5 @@
+use advent_of_code::Solution;
+
 fn parse(input: &str) -> Vec<&str> {
     input.lines().collect()
 }
@@ -39,11 +41,28 @@ pub fn part_two(input: &[&str]) -> Option<u32> {
     )
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<&'a str>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne> {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo> {
+        part_two(parsed)
+    }
+}
+
 fn main() {
-    let input = advent_of_code::read_file("inputs", 3);
-    let parsed = advent_of_code::run_parser(parse, &input);
-    advent_of_code::solve!(1, part_one, &parsed);
-    advent_of_code::solve!(2, part_two, &parsed);
+    advent_of_code::solve_day::<Puzzle>(2022, 3);
 }
 
 #[cfg(test)]
@@ -52,15 +71,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 3);
-        let parsed = advent_of_code::run_parser(parse, &input);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 3);
+        let parsed = parse(&input);
         assert_eq!(part_one(&parsed), Some(157));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 3);
-        let parsed = advent_of_code::run_parser(parse, &input);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 3);
+        let parsed = parse(&input);
         assert_eq!(part_two(&parsed), Some(70));
     }
 }
@@ -1,3 +1,5 @@
+use advent_of_code::Solution;
+
 pub struct Range {
     a: u32,
     b: u32,
@@ -47,11 +49,28 @@ pub fn part_two(input: &[(Range, Range)]) -> Option<usize> {
     Some(input.iter().filter(|(a, b)| a.overlaps(b)).count())
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<(Range, Range)>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne> {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo> {
+        part_two(parsed)
+    }
+}
+
 fn main() {
-    let input = advent_of_code::read_file("inputs", 4);
-    let parsed = advent_of_code::run_parser(parse, &input);
-    advent_of_code::solve!(1, part_one, &parsed);
-    advent_of_code::solve!(2, part_two, &parsed);
+    advent_of_code::solve_day::<Puzzle>(2022, 4);
 }
 
 #[cfg(test)]
@@ -60,14 +79,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 4);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 4);
         let parsed = parse(&input);
         assert_eq!(part_one(&parsed), Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 4);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 4);
         let parsed = parse(&input);
         assert_eq!(part_two(&parsed), Some(4));
     }
@@ -1,3 +1,5 @@
+use advent_of_code::Solution;
+
 fn parse(input: &str) -> &[u8] {
     input.as_bytes()
 }
@@ -30,27 +32,45 @@ pub fn part_two(input: &[u8]) -> Option<usize> {
     find_marker(input, 14)
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = &'a [u8];
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne> {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo> {
+        part_two(parsed)
+    }
+}
+
 fn main() {
-    let input = advent_of_code::read_file("inputs", 6);
-    let parsed = advent_of_code::run_parser(parse, &input);
-    advent_of_code::solve!(1, part_one, parsed);
-    advent_of_code::solve!(2, part_two, parsed);
+    advent_of_code::solve_day::<Puzzle>(2022, 6);
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 6);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 6);
         let parsed = parse(&input);
         assert_eq!(part_one(&parsed), Some(10));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 6);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 6);
         let parsed = parse(&input);
         assert_eq!(part_two(&parsed), Some(29));
     }
@@ -1,3 +1,4 @@
+use advent_of_code::Solution;
 use rayon::prelude::*;
 
 #[derive(Copy, Clone)]
@@ -179,11 +180,28 @@ pub fn part_two(input: &Vec<Blueprint>) -> Option<u32> {
     )
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<Blueprint>;
+    type PartOne = u32;
+    type PartTwo = u32;
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne> {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo> {
+        part_two(parsed)
+    }
+}
+
 fn main() {
-    let input = advent_of_code::read_file("inputs", 19);
-    let parsed = advent_of_code::run_parser(parse, &input);
-    advent_of_code::solve!(1, part_one, &parsed);
-    advent_of_code::solve!(2, part_two, &parsed);
+    advent_of_code::solve_day::<Puzzle>(2022, 19);
 }
 
 #[cfg(test)]
@@ -192,14 +210,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 19);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 19);
         let parsed = parse(&input);
         assert_eq!(part_one(&parsed), Some(33));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 19);
+        let input = advent_of_code::read_file_for_year(2022, "examples", 19);
         let parsed = parse(&input);
         assert_eq!(part_two(&parsed), Some(3472));
     }
@@ -2,22 +2,86 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::{
+    aoc_cli,
+    calendar::{self, Backoff, Clock, SystemClock},
+    examples, scaffold, template, Fetch,
+};
+use std::{
+    fs,
+    io::{self, Write},
+    process,
+};
 
 struct Args {
     day: u8,
     year: Option<u16>,
+    aoc_cli: bool,
+    wait: bool,
+    force: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
+        aoc_cli: args.contains("--aoc-cli"),
+        wait: args.contains("--wait"),
+        force: args.contains("--force"),
         year: args.opt_value_from_str(["-y", "--year"])?,
+        day: args.free_from_str()?,
     })
 }
 
+/// Counts down until the puzzle unlocks, then downloads it, retrying while the site catches up.
+fn wait_and_download(clock: &impl Clock, day: u8, year: u16, use_aoc_cli: bool, fetch: Fetch) {
+    calendar::wait_for_unlock(clock, year, day, |remaining| {
+        print!(
+            "\r⏳ Day {} unlocks in {}",
+            day,
+            calendar::format_countdown(remaining)
+        );
+        let _ = io::stdout().flush();
+    });
+    println!("\r🔓 Day {} is unlocked!{}", day, " ".repeat(16));
+
+    let result = calendar::retry(
+        clock,
+        &Backoff::default(),
+        || advent_of_code::download(year, day, use_aoc_cli, fetch),
+        |failure| failure.retryable,
+        |failure, delay| {
+            eprintln!(
+                "failed to download day {}: {}\nRetrying in {}s...",
+                day,
+                failure.message,
+                delay.as_secs()
+            )
+        },
+    );
+    if let Err(failure) = result {
+        eprintln!("failed to download day {}: {}", day, failure.message);
+        process::exit(1);
+    }
+}
+
+/// Scaffolds the solution of a day from the default template if it does not exist yet.
+fn scaffold_if_missing(day: u8, year: u16) {
+    if fs::metadata(advent_of_code::solution_path(year, day)).is_ok() {
+        return;
+    }
+    let module = scaffold::create_module(
+        year,
+        day,
+        template::DEFAULT_TEMPLATE,
+        template::ANSWER_TYPES[0],
+        Default::default(),
+        false,
+    );
+    if let Err(e) = module.and_then(|_| scaffold::register_day(year, day)) {
+        eprintln!("Could not scaffold day {}: {}", day, e);
+    }
+}
+
 fn main() {
     let args = match parse_args() {
         Ok(args) => args,
@@ -27,20 +91,35 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if args.aoc_cli {
+        if let Err(e) = aoc_cli::check() {
+            eprintln!("{}", e);
             process::exit(1);
         }
     }
+
+    let fetch = Fetch::plan(year, args.day, args.force);
+    if fetch.is_empty() {
+        println!(
+            "🎄 Day {} is already downloaded. Use --force to download it again.",
+            args.day
+        );
+    } else if args.wait {
+        wait_and_download(&SystemClock, args.day, year, args.aoc_cli, fetch);
+        scaffold_if_missing(args.day, year);
+    } else if let Err(failure) = advent_of_code::download(year, args.day, args.aoc_cli, fetch) {
+        eprintln!("failed to download day {}: {}", args.day, failure.message);
+        process::exit(1);
+    }
+
+    examples::write_default_example(year, args.day);
+    scaffold::fill_expected_answers(year, args.day);
 }
@@ -0,0 +1,133 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::examples;
+use std::{fs, process};
+
+/// Lines of each code block shown when listing them.
+const PREVIEW_LINES: usize = 3;
+
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    force: bool,
+    blocks: Vec<usize>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let year = args.opt_value_from_str(["-y", "--year"])?;
+    let force = args.contains("--force");
+    let day = args.free_from_str()?;
+    let mut blocks = Vec::new();
+    while let Some(block) = args.opt_free_from_str()? {
+        blocks.push(block);
+    }
+    Ok(Args {
+        day,
+        year,
+        force,
+        blocks,
+    })
+}
+
+fn list(blocks: &[String]) {
+    for (i, block) in blocks.iter().enumerate() {
+        let lines = block.lines().count();
+        println!(
+            "[{}] {} line{}:",
+            i + 1,
+            lines,
+            if lines == 1 { "" } else { "s" }
+        );
+        for line in block.lines().take(PREVIEW_LINES) {
+            println!("    {}", line);
+        }
+        if lines > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let path = advent_of_code::data_path(year, "puzzles", args.day, "md");
+    let puzzle = match fs::read_to_string(&path) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!(
+                "Could not read puzzle file \"{}\": {}. Try `cargo download {}` first.",
+                path, e, args.day
+            );
+            process::exit(1);
+        }
+    };
+
+    let blocks = examples::code_blocks(&puzzle);
+    if blocks.is_empty() {
+        println!("The puzzle of day {} has no code blocks.", args.day);
+        return;
+    }
+
+    if args.blocks.is_empty() {
+        list(&blocks);
+        println!("---");
+        let [one, two] = examples::expected_answers(&puzzle);
+        println!(
+            "Guessed example answers: part one {}, part two {}.",
+            one.as_deref().unwrap_or("unknown"),
+            two.as_deref().unwrap_or("unknown")
+        );
+        println!(
+            "Run `cargo example {} N [N...]` to write code blocks as examples.",
+            args.day
+        );
+        return;
+    }
+
+    let mut chosen = Vec::new();
+    for &n in &args.blocks {
+        match blocks.get(n.wrapping_sub(1)) {
+            Some(block) => chosen.push(block.as_str()),
+            None => {
+                eprintln!(
+                    "There is no code block {}. Pick one of 1-{}.",
+                    n,
+                    blocks.len()
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    match examples::write_examples(year, args.day, &chosen, args.force) {
+        Ok(written) => {
+            for path in &written {
+                println!("🎄 Successfully wrote example to \"{}\".", path);
+            }
+            if written.len() < chosen.len() {
+                println!("Kept existing examples. Pass --force to overwrite them.");
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to write examples: {}", e);
+            process::exit(1);
+        }
+    }
+}
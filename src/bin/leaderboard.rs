@@ -0,0 +1,128 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::client::Client;
+use advent_of_code::leaderboard::{self, Leaderboard};
+use advent_of_code::{ANSI_BOLD, ANSI_RESET};
+use std::{
+    fs, io, process,
+    time::{Duration, SystemTime},
+};
+
+/// The leaderboard endpoint should not be polled more often than this.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+struct Args {
+    id: u64,
+    year: Option<u16>,
+    day: Option<u8>,
+    offline: bool,
+    file: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        day: args.opt_value_from_str("--day")?,
+        offline: args.contains("--offline"),
+        file: args.opt_value_from_str("--file")?,
+        id: args.free_from_str()?,
+    })
+}
+
+fn load(path: &str) -> Option<Leaderboard> {
+    match Leaderboard::load(path) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Ignoring unreadable leaderboard \"{}\": {}", path, e);
+            None
+        }
+    }
+}
+
+fn is_fresh(path: &str) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age < REFRESH_INTERVAL)
+}
+
+/// Fetches the leaderboard and stores it, keeping the previous snapshot for deltas.
+fn fetch(year: u16, id: u64) -> Result<(), String> {
+    let json = Client::from_env()
+        .and_then(|client| client.leaderboard(year, id))
+        .map_err(|e| e.to_string())?;
+    Leaderboard::parse(&json).map_err(|e| format!("unexpected response: {}", e))?;
+
+    let cache = leaderboard::cache_path(year, id);
+    let save = || -> io::Result<()> {
+        fs::create_dir_all(format!("data/{}/leaderboards", year))?;
+        if fs::metadata(&cache).is_ok() {
+            fs::rename(&cache, leaderboard::previous_path(year, id))?;
+        }
+        fs::write(&cache, json)
+    };
+    save().map_err(|e| format!("could not save \"{}\": {}", cache, e))
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let cache = leaderboard::cache_path(year, args.id);
+    let (current, previous) = match &args.file {
+        Some(file) => (load(file), None),
+        None => {
+            if args.offline {
+                println!("Showing the snapshot from the last fetch.");
+            } else if is_fresh(&cache) {
+                println!(
+                    "Fetched less than 15 minutes ago, showing the snapshot from the last fetch."
+                );
+            } else if let Err(e) = fetch(year, args.id) {
+                eprintln!("Failed to fetch leaderboard: {}", e);
+                eprintln!("Falling back to the snapshot from the last fetch.");
+            }
+            (
+                load(&cache),
+                load(&leaderboard::previous_path(year, args.id)),
+            )
+        }
+    };
+
+    let current = match current {
+        Some(current) => current,
+        None => {
+            eprintln!(
+                "No snapshot of leaderboard {} found. Run `cargo leaderboard {}` while online first.",
+                args.id, args.id
+            );
+            process::exit(1);
+        }
+    };
+
+    let day = args.day.or(current.last_day()).unwrap_or(1);
+    let rows = leaderboard::rows(&current, previous.as_ref(), year, day);
+
+    println!(
+        "{}Private leaderboard {} ({}), star times for day {}{}",
+        ANSI_BOLD, args.id, year, day, ANSI_RESET
+    );
+    print!("{}", leaderboard::render_table(&rows));
+}
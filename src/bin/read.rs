@@ -2,22 +2,59 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::{aoc_cli, client::Client, render, solution};
+use std::{fs, io, process};
 
 struct Args {
     day: u8,
     year: Option<u16>,
+    part: Option<u8>,
+    aoc_cli: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
-        day: args.free_from_str()?,
+        aoc_cli: args.contains("--aoc-cli"),
         year: args.opt_value_from_str(["-y", "--year"])?,
+        part: args.opt_value_from_fn("--part", solution::parse_part)?,
+        day: args.free_from_str()?,
     })
 }
 
+fn read_with_aoc_cli(day: u8, year: u16) {
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+
+    if let Err(e) = aoc_cli::read(day, Some(year)) {
+        eprintln!("failed to read day {}: {}", day, e);
+        process::exit(1);
+    }
+}
+
+/// Reads the cached puzzle, falling back to fetching it when it has not been downloaded yet.
+fn load_puzzle(day: u8, year: u16) -> String {
+    let path = advent_of_code::data_path(year, "puzzles", day, "md");
+    match fs::read_to_string(&path) {
+        Ok(puzzle) => return puzzle,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => {
+            eprintln!("Could not read puzzle file \"{}\": {}", path, e);
+            process::exit(1);
+        }
+    }
+
+    match Client::from_env().and_then(|client| client.puzzle(year, day)) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!("failed to read day {}: {}", day, e);
+            process::exit(1);
+        }
+    }
+}
+
 fn main() {
     let args = match parse_args() {
         Ok(args) => args,
@@ -27,20 +64,33 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    match aoc_cli::read(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
+            eprintln!("Failed to process arguments: {}", e);
             process::exit(1);
         }
+    };
+
+    if args.aoc_cli {
+        read_with_aoc_cli(args.day, year);
+        return;
     }
+
+    let puzzle = load_puzzle(args.day, year);
+    let puzzle = match args.part {
+        Some(2) => match render::part_two(&puzzle) {
+            Some(part_two) => part_two,
+            None => {
+                eprintln!(
+                    "Part two of day {} is not unlocked yet. Solve part one and run `cargo download {}` again.",
+                    args.day, args.day
+                );
+                process::exit(1);
+            }
+        },
+        _ => &puzzle,
+    };
+
+    println!("{}", render::render(puzzle, render::terminal_width()));
 }
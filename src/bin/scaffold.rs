@@ -2,116 +2,228 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::{
+    launch,
+    scaffold::{self, MAIN_PATH},
+    template,
+};
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, OpenOptions},
+    io::{self, BufRead, Write},
+    path::Path,
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"#[allow(unused_variables)]
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    answers: [Option<String>; 2],
+    template: String,
+    answer_type: String,
+    dry_run: bool,
+    force: bool,
 }
 
-#[allow(unused_variables)]
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        answers: [
+            args.opt_value_from_str("--answer-one")?,
+            args.opt_value_from_str("--answer-two")?,
+        ],
+        template: args
+            .opt_value_from_str("--template")?
+            .unwrap_or_else(|| template::DEFAULT_TEMPLATE.into()),
+        answer_type: args
+            .opt_value_from_fn("--answer-type", template::parse_answer_type)?
+            .unwrap_or_else(|| template::ANSWER_TYPES[0].into()),
+        dry_run: args.contains("--dry-run"),
+        force: args.contains("--force"),
+        day: args.free_from_str()?,
+    })
 }
 
-fn main() {
-    let input = advent_of_code::read_file("inputs", DAY);
-    // let parsed = advent_of_code::run_parser(parse, &input);
-    advent_of_code::solve!(1, part_one, &input);
-    advent_of_code::solve!(2, part_two, &input);
+/// Creates an empty file, unless it exists. Returns whether it was created.
+fn create_empty_file(path: &str) -> io::Result<bool> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+fn confirm(question: &str) -> bool {
+    print!("{} [y/N] ", question);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
     }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+/// Lists what scaffolding a day would create or change, without touching anything.
+fn print_dry_run(year: u16, day: u8, force: bool) {
+    let module_path = advent_of_code::solution_path(year, day);
+    match fs::read_to_string(&module_path) {
+        Ok(source) => {
+            println!(
+                "Would overwrite module file \"{}\"{}",
+                module_path,
+                if force {
+                    ""
+                } else {
+                    " (pass --force to do so)"
+                }
+            );
+            match scaffold::is_untouched(year, day, &source) {
+                Ok(true) => println!("  It is unchanged from the template."),
+                Ok(false) => println!("  It has changes of its own, which would be lost."),
+                Err(e) => eprintln!("  Could not compare it with the templates: {}", e),
+            }
+        }
+        Err(_) => println!("Would create module file \"{}\"", module_path),
     }
-}
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
-}
+    match fs::read_to_string(MAIN_PATH) {
+        Ok(main) if scaffold::register(&main, year, day).is_some() => {
+            println!("Would register module in \"{}\"", MAIN_PATH)
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Could not read \"{}\": {}", MAIN_PATH, e),
+    }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
-}
+    match launch::missing_day(year, day) {
+        Ok(missing) if !missing.is_empty() => println!(
+            "Would add debug configurations to \"{}\"",
+            launch::LAUNCH_PATH
+        ),
+        Ok(_) => {}
+        Err(e) => eprintln!("Could not read \"{}\": {}", launch::LAUNCH_PATH, e),
+    }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    for (folder, name) in [("inputs", "input"), ("examples", "example")] {
+        let path = advent_of_code::data_path(year, folder, day, "txt");
+        if !Path::new(&path).exists() {
+            println!("Would create empty {} file \"{}\"", name, path);
+        }
+    }
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(pico_args::Error::MissingArgument) => {
             eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
             process::exit(1);
         }
-    };
-
-    let day_padded = format!("{:02}", day);
-
-    let input_path = format!("data/inputs/{}.txt", day_padded);
-    let example_path = format!("data/examples/{}.txt", day_padded);
-    let module_path = format!("src/bin/{}.rs", day_padded);
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
         Err(e) => {
-            eprintln!("Failed to create module file: {}", e);
+            eprintln!("Failed to process arguments: {}", e);
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
         Err(e) => {
-            eprintln!("Failed to write module contents: {}", e);
+            eprintln!("Failed to process arguments: {}", e);
             process::exit(1);
         }
+    };
+
+    let day = args.day;
+
+    let input_path = advent_of_code::data_path(year, "inputs", day, "txt");
+    let example_path = advent_of_code::data_path(year, "examples", day, "txt");
+    let module_path = advent_of_code::solution_path(year, day);
+
+    if let Err(e) = scaffold::load_template(&args.template) {
+        eprintln!("{}", e);
+        process::exit(1);
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {}", e);
+    // a dry run also previews what --force would overwrite.
+    if args.dry_run {
+        print_dry_run(year, day, args.force);
+        return;
+    }
+
+    let module_exists = Path::new(&module_path).exists();
+    if module_exists && !args.force {
+        eprintln!(
+            "Module file \"{}\" already exists. Pass --force to regenerate it from the template.",
+            module_path
+        );
+        process::exit(1);
+    }
+
+    if module_exists
+        && !confirm(&format!(
+            "Overwrite \"{}\" with a fresh copy of the template?",
+            module_path
+        ))
+    {
+        eprintln!("Aborted.");
+        process::exit(1);
+    }
+
+    for folder in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(format!("data/{}/{}", year, folder)) {
+            eprintln!("Failed to create data folder: {}", e);
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+    let module = match scaffold::create_module(
+        year,
+        day,
+        &args.template,
+        &args.answer_type,
+        args.answers,
+        args.force,
+    ) {
+        Ok(module) => module,
         Err(e) => {
-            eprintln!("Failed to create example file: {}", e);
+            eprintln!("{}", e);
             process::exit(1);
         }
+    };
+    for part in module.filled {
+        println!(
+            "Expecting {} for part {} of the example. Review it, or override it with --answer-{}.",
+            module.answers[part as usize - 1]
+                .as_deref()
+                .unwrap_or_default(),
+            part,
+            if part == 1 { "one" } else { "two" }
+        );
+    }
+    for part in module.skipped {
+        println!(
+            "Not expecting {} for part {} of the example, as it does not fit the answer type. Pass --answer-type or --answer-{}.",
+            module.answers[part as usize - 1].as_deref().unwrap_or_default(),
+            part,
+            if part == 1 { "one" } else { "two" }
+        );
+    }
+
+    if let Err(e) = scaffold::register_day(year, day) {
+        eprintln!("Failed to register module: {}", e);
+        process::exit(1);
+    }
+
+    for (path, name) in [(&input_path, "input"), (&example_path, "example")] {
+        match create_empty_file(path) {
+            Ok(true) => println!("Created empty {} file \"{}\"", name, path),
+            Ok(false) => println!("Keeping existing {} file \"{}\"", name, path),
+            Err(e) => {
+                eprintln!("Failed to create {} file: {}", name, e);
+                process::exit(1);
+            }
+        }
     }
 
     println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
+    println!("{}", scaffold::solve_hint(year, day));
 }
@@ -0,0 +1,125 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{aoc_cli, examples, render, scaffold, template, Fetch};
+use std::{fs, path::Path, process};
+
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    aoc_cli: bool,
+    template: String,
+    answer_type: String,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        aoc_cli: args.contains("--aoc-cli"),
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        template: args
+            .opt_value_from_str("--template")?
+            .unwrap_or_else(|| template::DEFAULT_TEMPLATE.into()),
+        answer_type: args
+            .opt_value_from_fn("--answer-type", template::parse_answer_type)?
+            .unwrap_or_else(|| template::ANSWER_TYPES[0].into()),
+        day: args.free_from_str()?,
+    })
+}
+
+/// Downloads whatever is still missing of the day's input and puzzle.
+fn download(day: u8, year: u16, use_aoc_cli: bool) {
+    let fetch = Fetch::plan(year, day, false);
+    if fetch.is_empty() {
+        println!("🎄 Day {} is already downloaded.", day);
+        return;
+    }
+
+    if let Err(failure) = advent_of_code::download(year, day, use_aoc_cli, fetch) {
+        eprintln!("failed to download day {}: {}", day, failure.message);
+        process::exit(1);
+    }
+}
+
+/// Creates the day's module from a template, or keeps the one a previous run created.
+fn create_module(day: u8, year: u16, template_name: &str, answer_type: &str) {
+    let path = advent_of_code::solution_path(year, day);
+
+    if Path::new(&path).exists() {
+        println!("🎄 Keeping existing module \"{}\".", path);
+        scaffold::fill_expected_answers(year, day);
+        return;
+    }
+
+    let module = match scaffold::create_module(
+        year,
+        day,
+        template_name,
+        answer_type,
+        Default::default(),
+        false,
+    ) {
+        Ok(module) => module,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    scaffold::print_expected_answers(&path, &module.answers, &module.filled, &module.skipped);
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+    let day = args.day;
+
+    if args.aoc_cli {
+        if let Err(e) = aoc_cli::check() {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+
+    for folder in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(format!("data/{}/{}", year, folder)) {
+            eprintln!("Failed to create data folder: {}", e);
+            process::exit(1);
+        }
+    }
+
+    download(day, year, args.aoc_cli);
+    create_module(day, year, &args.template, &args.answer_type);
+
+    if let Err(e) = scaffold::register_day(year, day) {
+        eprintln!("Failed to register module: {}", e);
+        process::exit(1);
+    }
+
+    examples::write_default_example(year, day);
+
+    let puzzle_path = advent_of_code::data_path(year, "puzzles", day, "md");
+    match fs::read_to_string(&puzzle_path) {
+        Ok(puzzle) => {
+            println!("---");
+            println!("{}", render::render(&puzzle, render::terminal_width()));
+        }
+        Err(e) => eprintln!("Could not read puzzle file \"{}\": {}", puzzle_path, e),
+    }
+
+    println!("---");
+    println!("{}", scaffold::solve_hint(year, day));
+}
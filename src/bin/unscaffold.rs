@@ -0,0 +1,116 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    launch,
+    scaffold::{self, MAIN_PATH},
+};
+use std::{fs, io, process};
+
+struct Args {
+    day: u8,
+    year: Option<u16>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        day: args.free_from_str()?,
+    })
+}
+
+/// Removes a file if it is empty. Returns whether it was removed.
+fn remove_if_empty(path: &str) -> io::Result<bool> {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.len() == 0 => fs::remove_file(path).map(|_| true),
+        Ok(_) => Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(pico_args::Error::MissingArgument) => {
+            eprintln!("Need to specify a day (as integer). example: `cargo unscaffold 7`");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+    let day = args.day;
+
+    let module_path = advent_of_code::solution_path(year, day);
+    let module_gone = match fs::read_to_string(&module_path) {
+        Ok(source) => match scaffold::is_untouched(year, day, &source) {
+            Ok(true) => match fs::remove_file(&module_path) {
+                Ok(_) => {
+                    println!("Removed module file \"{}\"", module_path);
+                    true
+                }
+                Err(e) => {
+                    eprintln!("Failed to remove module file: {}", e);
+                    process::exit(1);
+                }
+            },
+            Ok(false) => {
+                println!("Module file \"{}\" has changes, keeping it.", module_path);
+                false
+            }
+            Err(e) => {
+                eprintln!("Failed to compare module file with the templates: {}", e);
+                process::exit(1);
+            }
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => true,
+        Err(e) => {
+            eprintln!("Failed to read module file: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // a kept module still needs its registration, debug configurations and data files.
+    if !module_gone {
+        return;
+    }
+
+    match scaffold::unregister_module(year, day) {
+        Ok(true) => println!("Removed module from \"{}\"", MAIN_PATH),
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to unregister module: {}", e);
+            process::exit(1);
+        }
+    }
+
+    match launch::remove_day(year, day) {
+        Ok(removed) if !removed.is_empty() => println!(
+            "Removed debug configurations from \"{}\"",
+            launch::LAUNCH_PATH
+        ),
+        Ok(_) => {}
+        Err(e) => eprintln!("Could not update \"{}\": {}", launch::LAUNCH_PATH, e),
+    }
+
+    for (folder, name) in [("inputs", "input"), ("examples", "example")] {
+        let path = advent_of_code::data_path(year, folder, day, "txt");
+        match remove_if_empty(&path) {
+            Ok(true) => println!("Removed empty {} file \"{}\"", name, path),
+            Ok(false) => {}
+            Err(e) => eprintln!("Failed to remove {} file: {}", name, e),
+        }
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+/// See: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Unix timestamp at which a puzzle unlocks.
+pub fn unlock_time(year: u16, day: u8) -> u64 {
+    let days = days_from_civil(year as i64, 12, day as i64) as u64;
+    days * 86400 + UNLOCK_HOUR_UTC * 3600
+}
+
+/// Source of the current time, so that waiting can be tested without real time passing.
+pub trait Clock {
+    /// Current Unix timestamp in seconds.
+    fn now(&self) -> u64;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// Formats a countdown, e.g. `01:02:33`.
+pub fn format_countdown(secs: u64) -> String {
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Sleeps until a puzzle unlocks, calling `tick` with the remaining seconds about once a second.
+pub fn wait_for_unlock(clock: &impl Clock, year: u16, day: u8, mut tick: impl FnMut(u64)) {
+    let unlock = unlock_time(year, day);
+    loop {
+        let remaining = unlock.saturating_sub(clock.now());
+        if remaining == 0 {
+            return;
+        }
+        tick(remaining);
+        clock.sleep(Duration::from_secs(1));
+    }
+}
+
+/// How often and how patiently to retry a failing request.
+pub struct Backoff {
+    pub attempts: u32,
+    pub initial: Duration,
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            attempts: 8,
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Runs `attempt` until it succeeds, fails with an error that is not `retryable`, or runs out of
+/// attempts. The delay between attempts doubles each time, up to `backoff.max`.
+pub fn retry<T, E>(
+    clock: &impl Clock,
+    backoff: &Backoff,
+    mut attempt: impl FnMut() -> Result<T, E>,
+    retryable: impl Fn(&E) -> bool,
+    mut on_retry: impl FnMut(&E, Duration),
+) -> Result<T, E> {
+    let mut delay = backoff.initial;
+    let mut attempts = 1;
+    loop {
+        match attempt() {
+            Err(e) if attempts < backoff.attempts && retryable(&e) => {
+                on_retry(&e, delay);
+                clock.sleep(delay);
+                delay = (delay * 2).min(backoff.max);
+                attempts += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /// A clock that only moves when slept on.
+    struct FakeClock {
+        now: Cell<u64>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: u64) -> Self {
+            Self {
+                now: Cell::new(now),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> u64 {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration.as_secs());
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn test_wait_for_unlock() {
+        let clock = FakeClock::at(unlock_time(2022, 5) - 3);
+        let mut ticks = Vec::new();
+        wait_for_unlock(&clock, 2022, 5, |remaining| ticks.push(remaining));
+        assert_eq!(ticks, vec![3, 2, 1]);
+        assert_eq!(clock.now(), unlock_time(2022, 5));
+
+        // unlocked puzzles do not wait at all.
+        wait_for_unlock(&clock, 2022, 1, |_| panic!("should not wait"));
+    }
+
+    #[test]
+    fn test_retry() {
+        let clock = FakeClock::at(0);
+        let backoff = Backoff {
+            attempts: 5,
+            initial: Duration::from_secs(1),
+            max: Duration::from_secs(3),
+        };
+
+        let mut calls = 0;
+        let result: Result<u32, &str> = retry(
+            &clock,
+            &backoff,
+            || {
+                calls += 1;
+                if calls < 4 {
+                    Err("not yet")
+                } else {
+                    Ok(calls)
+                }
+            },
+            |_| true,
+            |_, _| {},
+        );
+        assert_eq!(result, Ok(4));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![1, 2, 3]
+                .into_iter()
+                .map(Duration::from_secs)
+                .collect::<Vec<_>>()
+        );
+
+        let mut calls = 0;
+        let result: Result<(), &str> = retry(
+            &clock,
+            &backoff,
+            || {
+                calls += 1;
+                Err("fatal")
+            },
+            |e| *e != "fatal",
+            |_, _| {},
+        );
+        assert_eq!((result, calls), (Err("fatal"), 1));
+
+        let result: Result<(), &str> = retry(&clock, &backoff, || Err("down"), |_| true, |_, _| {});
+        assert_eq!(result, Err("down"));
+        assert_eq!(clock.sleeps.borrow().len(), 3 + 4);
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlock_time(2022, 1), 1669870800);
+        // 2024-12-25T05:00:00Z
+        assert_eq!(unlock_time(2024, 25), 1735102800);
+    }
+}
@@ -0,0 +1,408 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::submit::{self, Submission};
+use crate::{data_path, Fetch};
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, create_dir_all},
+    io,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Overrides the base URL, e.g. to point the client at a local server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Holds the session cookie. Takes precedence over the session file.
+pub const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+/// Name of the session cookie file in the home directory, shared with aoc-cli.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+const USER_AGENT: &str = "github.com/pcheng17/advent-of-code-rust";
+
+/// Hint for a puzzle that is not unlocked yet, which the server reports as not found.
+pub const LOCKED_HINT: &str =
+    "The puzzle is not unlocked yet. Puzzles unlock at midnight EST (05:00 UTC).";
+
+/// Hint for a session cookie the server does not accept.
+pub const SESSION_HINT: &str = "The session cookie is missing or expired. Log in to adventofcode.com again and update ~/.adventofcode.session.";
+
+/// Suggests a fix for an error status of the Advent of Code server.
+pub fn status_hint(status: u16) -> Option<&'static str> {
+    match status {
+        404 => Some(LOCKED_HINT),
+        // the server answers 400 without a session and 500 with an invalid one.
+        400 | 500 => Some(SESSION_HINT),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Http { status: u16, url: String },
+    Transport(String),
+    Io(io::Error),
+}
+
+impl ClientError {
+    /// Whether the request might succeed when repeated, e.g. right after a puzzle unlocks.
+    /// An invalid session is reported as a 500, which no amount of retrying fixes.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Http { status, .. } => *status == 404 || *status > 500,
+            ClientError::Transport(_) => true,
+            ClientError::MissingSession | ClientError::Io(_) => false,
+        }
+    }
+
+    /// Suggests a fix for errors the server reports through status codes.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            ClientError::Http { status, .. } => status_hint(*status),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {} or write it to ~/{}.",
+                SESSION_ENV, SESSION_FILE
+            ),
+            ClientError::Http { status, url } => {
+                write!(f, "request to {} failed with status {}.", url, status)?;
+                if let Some(hint) = self.hint() {
+                    write!(f, "\n{}", hint)?;
+                }
+                Ok(())
+            }
+            ClientError::Transport(e) => write!(f, "request failed: {}", e),
+            ClientError::Io(e) => write!(f, "could not write output files to file system: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// Picks the session cookie from `value`, falling back to the contents of `file`.
+pub fn read_session(value: Option<String>, file: Option<&Path>) -> Option<String> {
+    value
+        .or_else(|| file.and_then(|f| fs::read_to_string(f).ok()))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn session_file() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(SESSION_FILE))
+}
+
+/// Converts a puzzle page into the markdown stored in `data/<year>/puzzles`.
+/// Only the `<main>` element is kept, which holds the puzzle parts and any accepted answers.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    let main = html
+        .find("<main>")
+        .and_then(|start| {
+            let end = html[start..].rfind("</main>")?;
+            Some(&html[start + "<main>".len()..start + end])
+        })
+        .unwrap_or(html);
+    html2md::parse_html(main)
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Creates a client from the session cookie in the environment or session file.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = read_session(env::var(SESSION_ENV).ok(), session_file().as_deref())
+            .ok_or(ClientError::MissingSession)?;
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(base_url, session))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn send(
+        &self,
+        method: &str,
+        path: &str,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, ClientError> {
+        let url = self.url(path);
+        let request = self
+            .agent
+            .request(method, &url)
+            .set("Cookie", &format!("session={}", self.session));
+        let result = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        let response = result.map_err(|e| match e {
+            ureq::Error::Status(status, _) => ClientError::Http {
+                status,
+                url: url.clone(),
+            },
+            ureq::Error::Transport(t) => ClientError::Transport(t.to_string()),
+        })?;
+        response.into_string().map_err(ClientError::Io)
+    }
+
+    fn get(&self, path: &str) -> Result<String, ClientError> {
+        self.send("GET", path, None)
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    /// Fetches the puzzle page and converts it to markdown.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let html = self.get(&format!("/{}/day/{}", year, day))?;
+        Ok(puzzle_to_markdown(&html))
+    }
+
+    /// Fetches the JSON of a private leaderboard.
+    pub fn leaderboard(&self, year: u16, id: u64) -> Result<String, ClientError> {
+        self.get(&format!("/{}/leaderboard/private/view/{}.json", year, id))
+    }
+
+    /// Submits an answer for one part of a day and parses the server's verdict.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, ClientError> {
+        let html = self.send(
+            "POST",
+            &format!("/{}/day/{}/answer", year, day),
+            Some(&[("level", &part.to_string()), ("answer", answer)]),
+        )?;
+        Ok(submit::parse_response(&html))
+    }
+
+    /// Writes the files selected by `fetch` to `data/<year>/inputs` and `data/<year>/puzzles`.
+    pub fn download(&self, year: u16, day: u8, fetch: Fetch) -> Result<(), ClientError> {
+        let input_path = data_path(year, "inputs", day, "txt");
+        let puzzle_path = data_path(year, "puzzles", day, "md");
+
+        let input = fetch.input.then(|| self.input(year, day)).transpose()?;
+        let puzzle = fetch.puzzle.then(|| self.puzzle(year, day)).transpose()?;
+
+        if let Some(input) = input {
+            create_dir_all(format!("data/{}/inputs", year))?;
+            fs::write(&input_path, input)?;
+            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        }
+        if let Some(puzzle) = puzzle {
+            create_dir_all(format!("data/{}/puzzles", year))?;
+            fs::write(&puzzle_path, puzzle)?;
+            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// A request received by the stand-in server.
+    struct Request {
+        method: String,
+        path: String,
+        cookie: Option<String>,
+        body: String,
+    }
+
+    /// Serves one canned response per request on a local port and reports what it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+
+                let mut cookie = None;
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    let header = header.trim_end();
+                    if header.is_empty() {
+                        break;
+                    }
+                    let (name, value) = header.split_once(": ").unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "cookie" => cookie = Some(value.to_string()),
+                        "content-length" => length = value.parse().unwrap(),
+                        _ => {}
+                    }
+                }
+
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                tx.send(Request {
+                    method,
+                    path,
+                    cookie,
+                    body: String::from_utf8(request_body).unwrap(),
+                })
+                .unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn test_read_session() {
+        assert_eq!(
+            read_session(Some(" abc\n".into()), None),
+            Some("abc".to_string())
+        );
+        assert_eq!(read_session(Some("".into()), None), None);
+        assert_eq!(
+            read_session(None, Some(Path::new("/nonexistent/session"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_puzzle_to_markdown() {
+        let html = r#"<html><body><header>nav</header><main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>A <em>star</em> fruit.</p>
+<pre><code>1000
+2000
+</code></pre></article>
+<p>Your puzzle answer was <code>72070</code>.</p></main><footer>x</footer></body></html>"#;
+        assert_eq!(
+            puzzle_to_markdown(html),
+            "\\--- Day 1: Calorie Counting ---\n----------\n\nA *star* fruit.\n\n```\n1000\n2000\n\n```\n\nYour puzzle answer was `72070`."
+        );
+    }
+
+    #[test]
+    fn test_fetches_input_and_puzzle() {
+        let (url, requests) = serve(vec![
+            (200, "1000\n2000\n"),
+            (200, "<main><p>Day <em>one</em></p></main>"),
+        ]);
+        let client = Client::new(url, "secret");
+
+        assert_eq!(client.input(2022, 1).unwrap(), "1000\n2000\n");
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/2022/day/1/input");
+        assert_eq!(request.cookie.as_deref(), Some("session=secret"));
+
+        assert_eq!(client.puzzle(2022, 1).unwrap(), "Day *one*");
+        assert_eq!(requests.recv().unwrap().path, "/2022/day/1");
+    }
+
+    #[test]
+    fn test_submits_answers() {
+        let (url, requests) = serve(vec![(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+        )]);
+        let client = Client::new(url, "secret");
+
+        assert_eq!(
+            client.submit(2022, 1, 2, "45000").unwrap(),
+            Submission::TooLow
+        );
+        let request = requests.recv().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/2022/day/1/answer");
+        assert_eq!(request.body, "level=2&answer=45000");
+    }
+
+    #[test]
+    fn test_reports_http_errors() {
+        let (url, _requests) = serve(vec![(404, "not yet")]);
+        let client = Client::new(url, "secret");
+        match client.input(2022, 25) {
+            Err(ClientError::Http { status, url }) => {
+                assert_eq!(status, 404);
+                assert!(url.ends_with("/2022/day/25/input"));
+            }
+            _ => panic!("expected an http error"),
+        }
+    }
+
+    #[test]
+    fn test_retryable() {
+        let http = |status| ClientError::Http {
+            status,
+            url: String::new(),
+        };
+        assert!(http(404).is_retryable());
+        assert!(http(503).is_retryable());
+        assert!(!http(500).is_retryable());
+        assert!(!http(400).is_retryable());
+        assert_eq!(http(500).hint(), Some(SESSION_HINT));
+    }
+}
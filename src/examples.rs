@@ -0,0 +1,272 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{
+    data_path, is_blank,
+    render::{self, Block},
+};
+use std::{fs, io};
+
+/// Path of an example file. Days with several examples use numbered files, e.g. `05-2.txt`.
+pub fn example_path(year: u16, day: u8, number: Option<usize>) -> String {
+    match number {
+        Some(n) => format!("data/{}/examples/{:02}-{}.txt", year, day, n),
+        None => data_path(year, "examples", day, "txt"),
+    }
+}
+
+/// Returns the contents of every fenced code block in a puzzle file.
+pub fn code_blocks(markdown: &str) -> Vec<String> {
+    render::blocks(markdown)
+        .filter_map(|block| match block {
+            Block::Code(code) => Some(code.join("\n") + "\n"),
+            Block::Line(_) => None,
+        })
+        .collect()
+}
+
+/// Writes the given code blocks as the examples of a day. A single block goes to `NN.txt`,
+/// several go to numbered files. Existing examples are kept unless `force` is set.
+/// Returns the paths that were written.
+pub fn write_examples(year: u16, day: u8, blocks: &[&str], force: bool) -> io::Result<Vec<String>> {
+    fs::create_dir_all(format!("data/{}/examples", year))?;
+
+    let mut written = Vec::new();
+    for (i, block) in blocks.iter().enumerate() {
+        let number = (blocks.len() > 1).then_some(i + 1);
+        let path = example_path(year, day, number);
+        if force || is_blank(&path) {
+            fs::write(&path, block)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+/// Writes the first code block of a downloaded puzzle as its example, unless an example exists.
+pub fn extract_default(year: u16, day: u8) -> io::Result<Option<String>> {
+    let puzzle = fs::read_to_string(data_path(year, "puzzles", day, "md"))?;
+    match code_blocks(&puzzle).first() {
+        Some(block) => Ok(write_examples(year, day, &[block], false)?.pop()),
+        None => Ok(None),
+    }
+}
+
+/// Extracts the default example like [`extract_default`] and tells the user what was written.
+pub fn write_default_example(year: u16, day: u8) {
+    match extract_default(year, day) {
+        Ok(Some(path)) => println!("🎄 Successfully wrote example to \"{}\".", path),
+        Ok(None) => {}
+        Err(e) => eprintln!("Could not extract an example: {}", e),
+    }
+}
+
+/// Marks the end of a solved part, after which no example results follow.
+const ANSWER_PREFIX: &str = "Your puzzle answer was";
+
+/// Returns the emphasized code spans of a line, written as `` `*x*` `` or `` *`x`* ``.
+fn emphasized_code(line: &str) -> Vec<&str> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(['`', '*']) {
+        let (open, close) = match &rest[start..] {
+            r if r.starts_with("`*") => ("`*", "*`"),
+            r if r.starts_with("*`") => ("*`", "`*"),
+            _ => {
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+        let inner = &rest[start + open.len()..];
+        match inner.find(close) {
+            Some(end) if end > 0 && !inner[..end].contains(['`', '*']) => {
+                spans.push(&inner[..end]);
+                rest = &inner[end + close.len()..];
+            }
+            _ => rest = &rest[start + 1..],
+        }
+    }
+    spans
+}
+
+/// Guesses the example answer of one part. Results are emphasized code, but so are intermediate
+/// values, which are usually listed as bullet points. A single result outside of a list wins;
+/// when every result is listed, the puzzle has several examples and the first one is used.
+/// Several results outside of lists are intermediate values the answer is derived from, so
+/// nothing is guessed.
+fn guess_answer(part: &str) -> Option<String> {
+    let part = part.split(ANSWER_PREFIX).next().unwrap_or_default();
+    let mut paragraphs = Vec::new();
+    let mut first_listed = None;
+
+    for line in part.lines() {
+        let spans = emphasized_code(line);
+        if line.trim_start().starts_with("* ") {
+            first_listed = first_listed.or(spans.first().copied());
+        } else {
+            paragraphs.extend(spans);
+        }
+    }
+
+    match paragraphs[..] {
+        [] => first_listed.map(|s| s.to_string()),
+        [span] => Some(span.to_string()),
+        _ => None,
+    }
+}
+
+/// Guesses the expected answers of the example for both parts of a puzzle.
+pub fn expected_answers(markdown: &str) -> [Option<String>; 2] {
+    match render::part_two(markdown) {
+        Some(part_two) => {
+            let part_one = &markdown[..markdown.len() - part_two.len()];
+            [guess_answer(part_one), guess_answer(part_two)]
+        }
+        None => [guess_answer(markdown), None],
+    }
+}
+
+const PART_TYPES: [&str; 2] = ["PartOne", "PartTwo"];
+const PART_FUNCTIONS: [&str; 2] = ["part_one", "part_two"];
+
+/// Formats an expected answer as a literal of the part's answer type, which is read from the
+/// `type PartOne = ...;` line of the solution. Returns `None` if the answer does not fit the type.
+fn answer_literal(source: &str, part: usize, answer: &str) -> Option<String> {
+    let declaration = format!("type {} = ", PART_TYPES[part]);
+    let answer_type = source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(&declaration)?.strip_suffix(';'))
+        .unwrap_or("u64");
+
+    match answer_type.trim() {
+        "String" => Some(format!("{:?}.to_string()", answer)),
+        t if t.starts_with('i') => answer.parse::<i64>().ok().map(|n| n.to_string()),
+        _ => answer.parse::<u64>().ok().map(|n| n.to_string()),
+    }
+}
+
+/// Whether a line is an untouched `assert_eq!(part_one(&...), None);` placeholder of a part.
+fn is_placeholder(line: &str, part: usize) -> bool {
+    let line = line.trim();
+    line.starts_with(&format!("assert_eq!({}(&", PART_FUNCTIONS[part]))
+        && line.ends_with("), None);")
+}
+
+/// A solution with expected answers filled into its tests.
+#[derive(Default)]
+pub struct Filled {
+    pub source: String,
+    /// The parts whose expected answers were filled in.
+    pub parts: Vec<u8>,
+    /// The parts whose expected answers were skipped, as they do not fit the answer type.
+    pub skipped: Vec<u8>,
+}
+
+/// Fills expected answers into the untouched `None` assertions of a scaffolded solution.
+/// Answers that do not fit the part's answer type are skipped.
+pub fn fill_expected_answers(source: &str, answers: &[Option<String>; 2]) -> Filled {
+    let mut lines: Vec<String> = source.split_inclusive('\n').map(String::from).collect();
+    let mut parts = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (i, answer) in answers.iter().enumerate() {
+        let answer = match answer {
+            Some(answer) => answer,
+            None => continue,
+        };
+        if let Some(line) = lines.iter_mut().find(|line| is_placeholder(line, i)) {
+            match answer_literal(source, i, answer) {
+                Some(literal) => {
+                    *line = line.replacen("), None);", &format!("), Some({}));", literal), 1);
+                    parts.push(i as u8 + 1);
+                }
+                None => skipped.push(i as u8 + 1),
+            }
+        }
+    }
+
+    Filled {
+        source: lines.concat(),
+        parts,
+        skipped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_blocks() {
+        let markdown =
+            "For example:\n\n```\n1000\n2000\n\n4000\n\n```\n\nAnd then:\n\n```\nA Y\n\n```\n";
+        assert_eq!(
+            code_blocks(markdown),
+            vec!["1000\n2000\n\n4000\n".to_string(), "A Y\n".to_string()]
+        );
+        assert!(code_blocks("no code `here`").is_empty());
+    }
+
+    #[test]
+    fn test_emphasized_code() {
+        assert_eq!(
+            emphasized_code("a total of `*6000*`, this is *`24000`* and `1000` *most*"),
+            vec!["6000", "24000"]
+        );
+    }
+
+    #[test]
+    fn test_expected_answers() {
+        let markdown = "* first Elf: `*6000*`\n* second Elf: `*4000*`\n\nIn the example above, this is *`24000`*.\n\nYour puzzle answer was `72070`.\n\n\\--- Part Two ---\n----------\n\n* `mjq`: `*19*`\n* `bvw`: `*23*`\n";
+        assert_eq!(
+            expected_answers(markdown),
+            [Some("24000".to_string()), Some("19".to_string())]
+        );
+        assert_eq!(expected_answers("no results"), [None, None]);
+    }
+
+    #[test]
+    fn test_expected_answers_derived() {
+        // the answers multiply the emphasized results, which must not be mistaken for them.
+        let markdown = include_str!("../data/2022/puzzles/19.md");
+        assert_eq!(expected_answers(markdown), [None, None]);
+        assert_eq!(
+            expected_answers("is `*9*`.\n\nis `*12*`, so `*21*` in total.\n"),
+            [None, None]
+        );
+    }
+
+    #[test]
+    fn test_fill_expected_answers() {
+        let source =
+            "assert_eq!(part_one(&input), None);\nassert_eq!(part_two(&input), Some(4));\n";
+        let filled = fill_expected_answers(source, &[Some("24000".into()), Some("45000".into())]);
+        assert_eq!(
+            filled.source,
+            "assert_eq!(part_one(&input), Some(24000));\nassert_eq!(part_two(&input), Some(4));\n"
+        );
+        assert_eq!(filled.parts, vec![1]);
+        assert!(filled.skipped.is_empty());
+
+        let filled = fill_expected_answers(source, &[Some("CMZ".into()), None]);
+        assert_eq!(filled.source, source);
+        assert!(filled.parts.is_empty());
+        assert_eq!(filled.skipped, vec![1]);
+
+        let source = "    type PartOne = String;\n    type PartTwo = i64;\n        assert_eq!(part_one(&parsed), None);\n        assert_eq!(part_two(&parsed), None);\n";
+        assert_eq!(
+            fill_expected_answers(source, &[Some("CMZ".into()), Some("-3".into())]).source,
+            "    type PartOne = String;\n    type PartTwo = i64;\n        assert_eq!(part_one(&parsed), Some(\"CMZ\".to_string()));\n        assert_eq!(part_two(&parsed), Some(-3));\n"
+        );
+    }
+
+    #[test]
+    fn test_example_path() {
+        assert_eq!(example_path(2022, 5, None), "data/2022/examples/05.txt");
+        assert_eq!(
+            example_path(2022, 5, Some(2)),
+            "data/2022/examples/05-2.txt"
+        );
+    }
+}
@@ -0,0 +1,435 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use serde_json::Value;
+use std::{fs, io};
+
+/// Debug configurations of VS Code, which scaffolded days are added to.
+pub const LAUNCH_PATH: &str = ".vscode/launch.json";
+
+/// Replaces `//` and `/* */` comments with spaces, so that the result is plain JSON with the same
+/// byte offsets as the original.
+fn blank_comments(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+    let mut in_string = false;
+
+    while i < bytes.len() {
+        match (in_string, bytes[i], bytes.get(i + 1)) {
+            (true, b'\\', _) => i += 1,
+            (true, b'"', _) | (false, b'"', _) => in_string = !in_string,
+            (false, b'/', Some(b'/')) => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out[i] = b' ';
+                    i += 1;
+                }
+                continue;
+            }
+            (false, b'/', Some(b'*')) => {
+                let end = source[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |e| i + 2 + e + 2);
+                for byte in &mut out[i..end] {
+                    if *byte != b'\n' {
+                        *byte = b' ';
+                    }
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    // comments are replaced as a whole, so no multi-byte character is split.
+    String::from_utf8(out).expect("blanking comments keeps UTF-8 intact")
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn parse(source: &str) -> io::Result<Value> {
+    let stripped = blank_comments(source);
+    // VS Code allows trailing commas, which serde_json does not.
+    let json: String = stripped
+        .char_indices()
+        .map(|(i, c)| {
+            let next = stripped[i + c.len_utf8()..].trim_start().chars().next();
+            match (c, next) {
+                (',', Some(']' | '}')) => ' ',
+                _ => c,
+            }
+        })
+        .collect();
+    serde_json::from_str(&json).map_err(|e| invalid(e.to_string()))
+}
+
+/// Byte offsets of the `[` and `]` of the top-level `configurations` array.
+fn configurations_array(json: &str) -> Option<(usize, usize)> {
+    let bytes = json.as_bytes();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut string_start = 0;
+    let mut array_start = None;
+    let mut after_key = false;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if in_string {
+            match byte {
+                b'\\' => i += 1,
+                b'"' => {
+                    in_string = false;
+                    after_key = depth == 1 && &json[string_start..i] == "configurations";
+                }
+                _ => {}
+            }
+        } else {
+            match byte {
+                b'"' => {
+                    in_string = true;
+                    string_start = i + 1;
+                }
+                b'{' | b'[' => {
+                    depth += 1;
+                    if byte == b'[' && after_key && array_start.is_none() {
+                        array_start = Some((i, depth));
+                    }
+                    after_key = false;
+                }
+                b'}' | b']' => {
+                    if let Some((start, array_depth)) = array_start {
+                        if byte == b']' && depth == array_depth {
+                            return Some((start, i));
+                        }
+                    }
+                    depth -= 1;
+                }
+                b':' => {}
+                b if b.is_ascii_whitespace() => {}
+                _ => after_key = false,
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// The debug configurations of a day's binary and of its unit tests, by name.
+fn day_configurations(bin: &str) -> [(String, String); 2] {
+    let package = env!("CARGO_PKG_NAME");
+    let configuration = |name: &str, args: &str| {
+        format!(
+            r#"        {{
+            "type": "lldb",
+            "request": "launch",
+            "name": "{name}",
+            "cargo": {{
+                "args": [{args}],
+                "filter": {{
+                    "name": "{bin}",
+                    "kind": "bin"
+                }}
+            }},
+            "args": [],
+            "cwd": "${{workspaceFolder}}"
+        }}"#
+        )
+    };
+
+    let executable = format!("Debug executable '{}'", bin);
+    let tests = format!("Debug unit tests in executable '{}'", bin);
+    [
+        (
+            executable.clone(),
+            configuration(
+                &executable,
+                &format!(r#""build", "--bin={}", "--package={}""#, bin, package),
+            ),
+        ),
+        (
+            tests.clone(),
+            configuration(
+                &tests,
+                &format!(
+                    r#""test", "--no-run", "--bin={}", "--package={}""#,
+                    bin, package
+                ),
+            ),
+        ),
+    ]
+}
+
+/// Adds the debug configurations of a binary to the contents of a `launch.json`, keeping its
+/// comments and formatting. Configurations with the same name are not added again.
+/// Returns the updated contents and the names of the added configurations.
+pub fn add_configurations(source: &str, bin: &str) -> io::Result<(String, Vec<String>)> {
+    let existing: Vec<String> = parse(source)?
+        .get("configurations")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid("no \"configurations\" array"))?
+        .iter()
+        .filter_map(|c| c.get("name")?.as_str().map(String::from))
+        .collect();
+
+    let (names, entries): (Vec<String>, Vec<String>) = day_configurations(bin)
+        .into_iter()
+        .filter(|(name, _)| !existing.contains(name))
+        .unzip();
+    if entries.is_empty() {
+        return Ok((source.to_string(), Vec::new()));
+    }
+
+    let stripped = blank_comments(source);
+    let (_, end) =
+        configurations_array(&stripped).ok_or_else(|| invalid("no \"configurations\" array"))?;
+    // insert right after the last element, before any comment or whitespace.
+    let last = stripped[..end].trim_end();
+    let separator = match last.chars().last() {
+        Some('[') | Some(',') => "\n",
+        _ => ",\n",
+    };
+    let closing = if last.ends_with('[') { "\n    " } else { "" };
+
+    let updated = format!(
+        "{}{}{}{}{}",
+        &source[..last.len()],
+        separator,
+        entries.join(",\n"),
+        closing,
+        &source[last.len()..]
+    );
+    parse(&updated)?;
+    Ok((updated, names))
+}
+
+/// Byte offsets of the first and last character of every element between `start` and `end`.
+fn elements(json: &str, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let bytes = json.as_bytes();
+    let mut spans = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut element_start = None;
+
+    let mut i = start;
+    while i < end {
+        let byte = bytes[i];
+        if in_string {
+            match byte {
+                b'\\' => i += 1,
+                b'"' => in_string = false,
+                _ => {}
+            }
+        } else {
+            match byte {
+                b'{' | b'[' => {
+                    if depth == 0 {
+                        element_start = Some(i);
+                    }
+                    depth += 1;
+                }
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        spans.extend(element_start.take().map(|s| (s, i)));
+                    }
+                }
+                b'"' => in_string = true,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Removes the debug configurations of a binary from the contents of a `launch.json`, keeping
+/// everything else as it is. Returns the updated contents and the names of the removed
+/// configurations.
+pub fn remove_configurations(source: &str, bin: &str) -> io::Result<(String, Vec<String>)> {
+    let names: Vec<String> = day_configurations(bin)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let mut updated = source.to_string();
+    let mut removed = Vec::new();
+
+    loop {
+        let stripped = blank_comments(&updated);
+        let (open, close) = configurations_array(&stripped)
+            .ok_or_else(|| invalid("no \"configurations\" array"))?;
+        let spans = elements(&stripped, open + 1, close);
+
+        let found = spans.iter().enumerate().find_map(|(i, &(start, end))| {
+            let name = parse(&stripped[start..=end])
+                .ok()?
+                .get("name")?
+                .as_str()?
+                .to_string();
+            names.contains(&name).then_some((i, name))
+        });
+        let (i, name) = match found {
+            Some(found) => found,
+            None => break,
+        };
+
+        let (start, end) = spans[i];
+        // take the separating comma along, from whichever side has one.
+        let range = match (spans.get(i + 1), i.checked_sub(1).map(|p| spans[p])) {
+            (Some(&(next, _)), _) => start..next,
+            (None, Some((_, previous))) => previous + 1..end + 1,
+            (None, None) => open + 1..end + 1,
+        };
+        updated.replace_range(range, "");
+        removed.push(name);
+    }
+
+    parse(&updated)?;
+    Ok((updated, removed))
+}
+
+/// The updated `.vscode/launch.json` and the names of the configurations missing for a day.
+/// Projects without a `launch.json` need none.
+fn updated_day(year: u16, day: u8) -> io::Result<Option<(String, Vec<String>)>> {
+    let source = match fs::read_to_string(LAUNCH_PATH) {
+        Ok(source) => source,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    add_configurations(&source, &format!("{}-{:02}", year, day)).map(Some)
+}
+
+/// Names of the debug configurations of a day that `.vscode/launch.json` does not have yet.
+pub fn missing_day(year: u16, day: u8) -> io::Result<Vec<String>> {
+    Ok(updated_day(year, day)?
+        .map(|(_, added)| added)
+        .unwrap_or_default())
+}
+
+/// Adds the debug configurations of a day to `.vscode/launch.json`, if the project has one.
+/// Returns the names of the added configurations.
+pub fn add_day(year: u16, day: u8) -> io::Result<Vec<String>> {
+    match updated_day(year, day)? {
+        Some((updated, added)) if !added.is_empty() => {
+            fs::write(LAUNCH_PATH, updated)?;
+            Ok(added)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Removes the debug configurations of a day from `.vscode/launch.json`, if the project has one.
+/// Returns the names of the removed configurations.
+pub fn remove_day(year: u16, day: u8) -> io::Result<Vec<String>> {
+    let source = match fs::read_to_string(LAUNCH_PATH) {
+        Ok(source) => source,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let (updated, removed) = remove_configurations(&source, &format!("{}-{:02}", year, day))?;
+    if !removed.is_empty() {
+        fs::write(LAUNCH_PATH, updated)?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAUNCH: &str = r#"{
+    // Use IntelliSense to learn about possible attributes.
+    "version": "0.2.0",
+    "configurations": [
+        {
+            "name": "Debug executable 'advent_of_code'", /* the runner */
+            "cwd": "${workspaceFolder}"
+        } // last one
+    ]
+}"#;
+
+    fn names(source: &str) -> Vec<String> {
+        parse(source).unwrap()["configurations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_add_configurations() {
+        let (updated, added) = add_configurations(LAUNCH, "2022-05").unwrap();
+        assert_eq!(
+            added,
+            vec![
+                "Debug executable '2022-05'",
+                "Debug unit tests in executable '2022-05'"
+            ]
+        );
+        assert_eq!(
+            names(&updated),
+            vec![
+                "Debug executable 'advent_of_code'",
+                "Debug executable '2022-05'",
+                "Debug unit tests in executable '2022-05'"
+            ]
+        );
+        assert!(updated.contains("/* the runner */"));
+        assert!(updated.contains("} // last one"));
+        assert!(
+            updated.contains(r#""args": ["build", "--bin=2022-05", "--package=advent_of_code"],"#)
+        );
+
+        // existing configurations are kept as they are.
+        let (again, added) = add_configurations(&updated, "2022-05").unwrap();
+        assert_eq!((again, added.len()), (updated, 0));
+    }
+
+    #[test]
+    fn test_add_configurations_edge_cases() {
+        let empty = "{\"configurations\": []}";
+        let (updated, _) = add_configurations(empty, "2022-01").unwrap();
+        assert_eq!(names(&updated).len(), 2);
+
+        let trailing = "{\"configurations\": [{\"name\": \"a\"},]}";
+        let (updated, _) = add_configurations(trailing, "2022-01").unwrap();
+        assert_eq!(names(&updated).len(), 3);
+
+        assert!(add_configurations("{\"version\": \"0.2.0\"}", "2022-01").is_err());
+        assert!(add_configurations("not json", "2022-01").is_err());
+    }
+
+    #[test]
+    fn test_remove_configurations() {
+        let (added, _) = add_configurations(LAUNCH, "2022-05").unwrap();
+        let (both, _) = add_configurations(&added, "2022-06").unwrap();
+
+        let (updated, removed) = remove_configurations(&both, "2022-05").unwrap();
+        assert_eq!(removed.len(), 2);
+        assert_eq!(
+            names(&updated),
+            vec![
+                "Debug executable 'advent_of_code'",
+                "Debug executable '2022-06'",
+                "Debug unit tests in executable '2022-06'"
+            ]
+        );
+        assert!(updated.contains("/* the runner */"));
+
+        // removing what was added restores the original, comments included.
+        let (updated, _) = remove_configurations(&updated, "2022-06").unwrap();
+        assert_eq!(updated, LAUNCH);
+        let (again, removed) = remove_configurations(LAUNCH, "2022-06").unwrap();
+        assert_eq!((again.as_str(), removed.len()), (LAUNCH, 0));
+
+        let (only, _) = add_configurations("{\"configurations\": []}", "2022-01").unwrap();
+        let (updated, _) = remove_configurations(&only, "2022-01").unwrap();
+        assert_eq!(names(&updated).len(), 0);
+    }
+}
@@ -0,0 +1,309 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::calendar::unlock_time;
+use crate::{load_json, parse_json, ANSI_BOLD, ANSI_RESET};
+use serde::Deserialize;
+use std::{collections::BTreeMap, io, path::Path};
+
+/// Path of the last fetched snapshot of a private leaderboard.
+pub fn cache_path(year: u16, id: u64) -> String {
+    format!("data/{}/leaderboards/{}.json", year, id)
+}
+
+/// Path of the snapshot before the last fetch, which deltas are computed against.
+pub fn previous_path(year: u16, id: u64) -> String {
+    format!("data/{}/leaderboards/{}.previous.json", year, id)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    pub get_star_ts: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub stars: u32,
+    #[serde(default)]
+    pub local_score: u64,
+    #[serde(default)]
+    pub last_star_ts: u64,
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Timestamp at which the member got the star for a part of a day.
+    pub fn star(&self, day: u8, part: u8) -> Option<u64> {
+        Some(self.completion_day_level.get(&day)?.get(&part)?.get_star_ts)
+    }
+}
+
+/// A private leaderboard as returned by `/<year>/leaderboard/private/view/<id>.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub members: BTreeMap<String, Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> io::Result<Self> {
+        parse_json(json)
+    }
+
+    /// Loads a snapshot from `path`. A missing file yields `None`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        load_json(path)
+    }
+
+    /// Members ordered by local score, then stars, then who got their last star first.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+        });
+        members
+    }
+
+    /// The latest day anyone got a star on.
+    pub fn last_day(&self) -> Option<u8> {
+        self.members
+            .values()
+            .filter_map(|m| m.completion_day_level.keys().max().copied())
+            .max()
+    }
+}
+
+/// One line of the rendered leaderboard.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Row {
+    pub rank: usize,
+    pub name: String,
+    pub score: u64,
+    /// Score gained since the previous snapshot.
+    pub score_delta: Option<u64>,
+    /// Places gained (positive) or lost (negative) since the previous snapshot.
+    pub rank_delta: Option<i64>,
+    pub stars: String,
+    /// Time from unlock until each star of the selected day.
+    pub times: [Option<u64>; 2],
+}
+
+fn star_cell(member: &Member, day: u8) -> char {
+    match (member.star(day, 1), member.star(day, 2)) {
+        (Some(_), Some(_)) => '★',
+        (Some(_), None) => '☆',
+        _ => '·',
+    }
+}
+
+pub fn rows(current: &Leaderboard, previous: Option<&Leaderboard>, year: u16, day: u8) -> Vec<Row> {
+    let last_day = current.last_day().unwrap_or(day).max(day);
+    let previous_ranking = previous.map(|p| p.ranking());
+
+    current
+        .ranking()
+        .iter()
+        .enumerate()
+        .map(|(i, member)| {
+            let before = previous_ranking.as_ref().map(|ranking| {
+                ranking
+                    .iter()
+                    .position(|m| m.id == member.id)
+                    .map(|rank| (rank, ranking[rank].local_score))
+            });
+            let (score_delta, rank_delta) = match before {
+                Some(Some((rank, score))) => (
+                    Some(member.local_score.saturating_sub(score)),
+                    Some(rank as i64 - i as i64),
+                ),
+                // new members gained everything since the previous snapshot.
+                Some(None) => (Some(member.local_score), None),
+                None => (None, None),
+            };
+
+            let unlock = unlock_time(year, day);
+            Row {
+                rank: i + 1,
+                name: member.display_name(),
+                score: member.local_score,
+                score_delta,
+                rank_delta,
+                stars: (1..=last_day).map(|d| star_cell(member, d)).collect(),
+                times: [1, 2]
+                    .map(|part| member.star(day, part).map(|ts| ts.saturating_sub(unlock))),
+            }
+        })
+        .collect()
+}
+
+/// Formats the time it took to get a star, e.g. `01:02:33`.
+pub fn format_elapsed(secs: u64) -> String {
+    if secs >= 100 * 3600 {
+        return ">99h".into();
+    }
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn format_rank_delta(delta: Option<i64>) -> String {
+    match delta {
+        Some(d) if d > 0 => format!("▲{}", d),
+        Some(d) if d < 0 => format!("▼{}", -d),
+        _ => String::new(),
+    }
+}
+
+pub fn render_table(rows: &[Row]) -> String {
+    let name_width = rows
+        .iter()
+        .map(|r| r.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+    let stars_width = rows
+        .first()
+        .map_or(0, |r| r.stars.chars().count())
+        .max("Stars".len());
+
+    let mut out = format!(
+        "{}{:>3}  {:<name_width$}  {:>5}  {:>5}  {:<stars_width$}  {:>8}  {:>8}{}\n",
+        ANSI_BOLD, "#", "Name", "Score", "Δ", "Stars", "★1", "★2", ANSI_RESET,
+    );
+
+    for row in rows {
+        let delta = match row.score_delta {
+            Some(0) | None => String::new(),
+            Some(d) => format!("+{}", d),
+        };
+        let [one, two] = row
+            .times
+            .map(|t| t.map(format_elapsed).unwrap_or_else(|| "-".into()));
+        let line = format!(
+            "{:>3}) {:<name_width$}  {:>5}  {:>5}  {:<stars_width$}  {:>8}  {:>8}  {}",
+            row.rank,
+            row.name,
+            row.score,
+            delta,
+            row.stars,
+            one,
+            two,
+            format_rank_delta(row.rank_delta),
+        );
+        // empty deltas at the end of a row would leave trailing whitespace.
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT: &str = r#"{
+        "owner_id": 1,
+        "event": "2022",
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1669960000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1669871523, "star_index": 0 }, "2": { "get_star_ts": 1669874553, "star_index": 1 } },
+                    "2": { "1": { "get_star_ts": 1669960000, "star_index": 2 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 12, "global_score": 0,
+                "last_star_ts": 1669880000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1669870900, "star_index": 0 }, "2": { "get_star_ts": 1669871000, "star_index": 1 } }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_ranking() {
+        let leaderboard = Leaderboard::parse(SNAPSHOT).unwrap();
+        let ranking: Vec<String> = leaderboard
+            .ranking()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(ranking, vec!["(anonymous user #2)", "Alice"]);
+        assert_eq!(leaderboard.last_day(), Some(2));
+    }
+
+    #[test]
+    fn test_rows() {
+        let current = Leaderboard::parse(SNAPSHOT).unwrap();
+        let mut previous = current.clone();
+        previous.members.get_mut("1").unwrap().local_score = 20;
+        previous.members.get_mut("2").unwrap().local_score = 5;
+
+        let rows = rows(&current, Some(&previous), 2022, 1);
+        assert_eq!(
+            rows[0],
+            Row {
+                rank: 1,
+                name: "(anonymous user #2)".into(),
+                score: 12,
+                score_delta: Some(7),
+                rank_delta: Some(1),
+                stars: "★·".into(),
+                times: [Some(100), Some(200)],
+            }
+        );
+        assert_eq!(rows[1].stars, "★☆");
+        assert_eq!(rows[1].rank_delta, Some(-1));
+        assert_eq!(rows[1].times, [Some(723), Some(3753)]);
+    }
+
+    #[test]
+    fn test_render_table() {
+        let current = Leaderboard::parse(SNAPSHOT).unwrap();
+        let mut previous = current.clone();
+        previous.members.get_mut("1").unwrap().local_score = 20;
+        previous.members.get_mut("2").unwrap().local_score = 5;
+
+        let table = render_table(&rows(&current, Some(&previous), 2022, 1))
+            .replace(ANSI_BOLD, "")
+            .replace(ANSI_RESET, "");
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "  #  Name                 Score      Δ  Stars        ★1        ★2",
+                "  1) (anonymous user #2)     12     +7  ★·     00:01:40  00:03:20  ▲1",
+                "  2) Alice                   10         ★☆     00:12:03  01:02:33  ▼1",
+            ]
+        );
+
+        // every column starts at the same offset in the header and the rows.
+        let column = |line: &str, text: &str| line[..line.find(text).unwrap()].chars().count();
+        assert_eq!(column(lines[0], "Name"), column(lines[1], "(anonymous"));
+        assert_eq!(column(lines[0], "Name"), column(lines[2], "Alice"));
+        assert_eq!(column(lines[0], "Stars"), column(lines[1], "★·"));
+        assert!(lines.iter().all(|line| line.trim_end() == *line));
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(3753), "01:02:33");
+        assert_eq!(format_elapsed(360_000), ">99h");
+    }
+}
@@ -3,106 +3,207 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
+use serde::{de::DeserializeOwned, Serialize};
 use std::env;
 use std::fs;
-
+use std::io;
+use std::path::Path;
+
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod calendar;
+pub mod client;
+pub mod examples;
 pub mod helpers;
+pub mod launch;
+pub mod leaderboard;
+pub mod output;
+pub mod render;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod template;
+
+pub use solution::{solve_day, Solution};
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-#[macro_export]
-macro_rules! solve {
-    ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<I, T: Display>(func: impl FnOnce(I) -> Option<T>, input: I) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_RESET, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
+/// Environment variable holding the default year, set in `.cargo/config`.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+pub fn default_year() -> Option<u16> {
+    env::var(YEAR_ENV).ok()?.trim().parse().ok()
+}
+
+/// Returns `year` if given, otherwise the default year.
+pub fn resolve_year(year: Option<u16>) -> Result<u16, String> {
+    year.or_else(default_year).ok_or_else(|| {
+        format!(
+            "no year given. Pass `--year` or set {} in .cargo/config.",
+            YEAR_ENV
+        )
+    })
+}
+
+/// Path of a day's file relative to the project root, e.g. `data/2022/inputs/01.txt`.
+pub fn data_path(year: u16, folder: &str, day: u8, extension: &str) -> String {
+    format!("data/{}/{}/{:02}.{}", year, folder, day, extension)
+}
+
+/// Selects a named input profile, e.g. `alice` for `data/2022/inputs/alice/01.txt`.
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+
+/// Returns `profile` if given, otherwise the profile set in the environment.
+pub fn resolve_profile(profile: Option<String>) -> Option<String> {
+    profile
+        .or_else(|| env::var(PROFILE_ENV).ok())
+        .filter(|p| !p.trim().is_empty())
+}
+
+/// Path of a day's input for a profile, e.g. `data/2022/inputs/alice/01.txt`. Without a profile
+/// this is the input of the session's own account.
+pub fn input_path(year: u16, day: u8, profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => data_path(year, &format!("inputs/{}", profile), day, "txt"),
+        None => data_path(year, "inputs", day, "txt"),
+    }
+}
+
+/// Names of the input profiles of a year, i.e. the folders in `data/<year>/inputs`.
+pub fn profiles(year: u16) -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(format!("data/{}/inputs", year)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut profiles = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            profiles.push(entry.file_name().to_string_lossy().into_owned());
         }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+
+pub fn try_read_input(year: u16, day: u8, profile: Option<&str>) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    fs::read_to_string(cwd.join(input_path(year, day, profile)))
+}
+
+/// Path of a day's solution, e.g. `src/bin/2022-01.rs`.
+pub fn solution_path(year: u16, day: u8) -> String {
+    format!("src/bin/{}-{:02}.rs", year, day)
+}
 
-        print!("🎄 {}Part {}{}: ", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
-    }};
+/// Whether a file is missing or empty, i.e. safe to write to without losing anything.
+pub fn is_blank(path: impl AsRef<Path>) -> bool {
+    !fs::metadata(path).is_ok_and(|m| m.len() > 0)
 }
 
-pub fn run_parser<'a, T>(func: impl Fn(&'a str) -> T, input: &'a str) -> T {
-    fn print_result<'a, T>(func: impl FnOnce(&'a str) -> T, input: &'a str) -> T {
-        let timer = std::time::Instant::now();
-        let result = func(input);
-        let elapsed = timer.elapsed();
-        println!("✓ (elapsed: {:.2?})", elapsed);
-        result
+/// Parses JSON, reporting malformed contents as `InvalidData`.
+pub fn parse_json<T: DeserializeOwned>(json: &str) -> io::Result<T> {
+    serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Reads a JSON file. A missing file yields `None`.
+pub fn load_json<T: DeserializeOwned>(path: impl AsRef<Path>) -> io::Result<Option<T>> {
+    match fs::read_to_string(path) {
+        Ok(json) => parse_json(&json).map(Some),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
     }
-    print!("🎄 {}Parser{}: ", ANSI_BOLD, ANSI_RESET);
-    print_result(func, input)
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{:02}.txt", day));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+/// Writes a value as pretty-printed JSON, ending with a newline.
+pub fn save_json<T: Serialize>(path: impl AsRef<Path>, value: &T) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, json + "\n")
 }
 
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
+/// The files of a day that a download should fetch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fetch {
+    pub input: bool,
+    pub puzzle: bool,
 }
 
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats,
-            // see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
+/// Shown below a puzzle once both of its parts are solved.
+const BOTH_PARTS_COMPLETE: &str = "Both parts of this puzzle are complete!";
+
+impl Fetch {
+    /// Inputs never change, so an existing one is kept. A puzzle is only fetched again until
+    /// part two is solved locally, i.e. has its answer, or on the last day, the closing
+    /// message. `force` fetches both regardless.
+    pub fn plan(year: u16, day: u8, force: bool) -> Self {
+        let puzzle = fs::read_to_string(data_path(year, "puzzles", day, "md")).unwrap_or_default();
+        let solved = render::part_two(&puzzle).is_some()
+            && (answers::parse_puzzle_answers(&puzzle)[1].is_some()
+                || puzzle.contains(BOTH_PARTS_COMPLETE));
+        Self {
+            input: force || is_blank(data_path(year, "inputs", day, "txt")),
+            puzzle: force || !solved,
         }
-    })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.input && !self.puzzle
+    }
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+/// A failed download, and whether trying again might help.
+#[derive(Debug)]
+pub struct DownloadError {
+    pub message: String,
+    pub retryable: bool,
+}
+
+/// Downloads the files selected by `fetch`, either through aoc-cli or with the built-in client.
+/// Callers using aoc-cli should [`aoc_cli::check`] for it first, before retrying or waiting.
+pub fn download(year: u16, day: u8, use_aoc_cli: bool, fetch: Fetch) -> Result<(), DownloadError> {
+    if use_aoc_cli {
+        aoc_cli::download(day, year, fetch)
+            .map(|_| ())
+            .map_err(|e| DownloadError {
+                retryable: e.is_retryable(),
+                message: e.to_string(),
+            })
+    } else {
+        client::Client::from_env()
+            .and_then(|client| client.download(year, day, fetch))
+            .map_err(|e| DownloadError {
+                retryable: e.is_retryable(),
+                message: e.to_string(),
+            })
+    }
+}
+
+pub fn try_read_file(year: u16, folder: &str, day: u8) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    fs::read_to_string(cwd.join(data_path(year, folder, day, "txt")))
+}
+
+pub fn read_file_for_year(year: u16, folder: &str, day: u8) -> String {
+    let f = try_read_file(year, folder, day);
+    f.expect("could not open input file")
+}
+
+/// Reads one of several numbered examples of a day, e.g. `data/2022/examples/05-2.txt`.
+pub fn read_example(year: u16, day: u8, number: usize) -> String {
+    let path = examples::example_path(year, day, Some(number));
+    fs::read_to_string(path).expect("could not open example file")
+}
+
+/// Reads a day's file for the default year.
+pub fn read_file(folder: &str, day: u8) -> String {
+    let year = resolve_year(None).unwrap_or_else(|e| panic!("{}", e));
+    read_file_for_year(year, folder, day)
 }
 
 #[cfg(test)]
@@ -110,114 +211,189 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
+    fn test_input_path() {
+        assert_eq!(input_path(2022, 1, None), "data/2022/inputs/01.txt");
+        assert_eq!(
+            input_path(2022, 1, Some("alice")),
+            "data/2022/inputs/alice/01.txt"
         );
     }
 }
 
 pub mod aoc_cli {
+    use crate::client::{self, LOCKED_HINT, SESSION_HINT};
+    use crate::{data_path, Fetch};
     use std::{
+        env,
         fmt::Display,
         fs::create_dir_all,
+        io,
         process::{Command, Output, Stdio},
     };
 
+    /// Overrides the aoc-cli executable, either by name or by path.
+    pub const PROGRAM_ENV: &str = "AOC_CLI";
+
+    const DEFAULT_PROGRAM: &str = "aoc";
+
+    pub fn program() -> String {
+        env::var(PROGRAM_ENV).unwrap_or_else(|_| DEFAULT_PROGRAM.into())
+    }
+
+    fn spawn_error(e: io::Error) -> AocCliError {
+        match e.kind() {
+            io::ErrorKind::NotFound => AocCliError::CommandNotFound(e),
+            _ => AocCliError::CommandNotCallable(e),
+        }
+    }
+
+    #[derive(Debug)]
     pub enum AocCliError {
-        CommandNotFound,
-        CommandNotCallable,
-        BadExitStatus(Output),
-        IoError,
+        CommandNotFound(io::Error),
+        CommandNotCallable(io::Error),
+        BadExitStatus { code: Option<i32>, stderr: String },
+        IoError(io::Error),
+    }
+
+    /// The status of a failed request, as aoc-cli reports it, e.g.
+    /// `HTTP status client error (400 Bad Request) for url (...)`.
+    fn http_status(stderr: &str) -> Option<u16> {
+        let (_, rest) = stderr.split_once("HTTP status ")?;
+        let (_, rest) = rest.split_once('(')?;
+        rest.get(..3)?.parse().ok()
+    }
+
+    impl AocCliError {
+        /// Suggests a fix based on what aoc-cli printed before failing.
+        pub fn hint(&self) -> Option<&'static str> {
+            let stderr = match self {
+                AocCliError::CommandNotFound(_) => {
+                    return Some("Try running \"cargo install aoc-cli\" to install it, or set AOC_CLI to its path.")
+                }
+                AocCliError::BadExitStatus { stderr, .. } => stderr,
+                _ => return None,
+            };
+
+            let lowercase = stderr.to_lowercase();
+            if lowercase.contains("is still locked") {
+                Some(LOCKED_HINT)
+            } else if lowercase.contains("session cookie") {
+                Some(SESSION_HINT)
+            } else {
+                http_status(stderr).and_then(client::status_hint)
+            }
+        }
+
+        /// Whether calling aoc-cli again might help, i.e. it failed for a reason other than the
+        /// session cookie.
+        pub fn is_retryable(&self) -> bool {
+            matches!(self, AocCliError::BadExitStatus { .. }) && self.hint() != Some(SESSION_HINT)
+        }
     }
 
     impl Display for AocCliError {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
-                AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
+                AocCliError::CommandNotFound(e) => {
+                    write!(f, "aoc-cli is not present in environment: {}", e)?
+                }
+                AocCliError::CommandNotCallable(e) => {
+                    write!(f, "aoc-cli could not be called: {}", e)?
+                }
+                AocCliError::BadExitStatus { code, stderr } => {
+                    match code {
+                        Some(code) => write!(f, "aoc-cli exited with status {}.", code)?,
+                        None => write!(f, "aoc-cli was terminated by a signal.")?,
+                    }
+                    if !stderr.trim().is_empty() {
+                        write!(f, "\n{}", stderr.trim_end())?;
+                    }
+                }
+                AocCliError::IoError(e) => {
+                    write!(f, "could not write output files to file system: {}", e)?
                 }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
+            }
+            if let Some(hint) = self.hint() {
+                write!(f, "\n{}", hint)?;
+            }
+            Ok(())
+        }
+    }
+
+    impl std::error::Error for AocCliError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                AocCliError::CommandNotFound(e)
+                | AocCliError::CommandNotCallable(e)
+                | AocCliError::IoError(e) => Some(e),
+                AocCliError::BadExitStatus { .. } => None,
+            }
+        }
+    }
+
+    impl From<Output> for AocCliError {
+        fn from(output: Output) -> Self {
+            AocCliError::BadExitStatus {
+                code: output.status.code(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
             }
         }
     }
 
     pub fn check() -> Result<(), AocCliError> {
-        Command::new("aoc")
+        Command::new(program())
             .arg("-V")
             .output()
-            .map_err(|_| AocCliError::CommandNotFound)?;
+            .map_err(spawn_error)?;
         Ok(())
     }
 
     pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
         let args = build_args("read", &[], day, year);
-        call_aoc_cli(&args)
-    }
-
-    pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        let input_path = get_input_path(day);
-
-        let puzzle_path = get_puzzle_path(day);
-        create_dir_all("data/puzzles").map_err(|_| AocCliError::IoError)?;
-
-        let args = build_args(
-            "download",
-            &[
-                "--overwrite".into(),
-                "--input-file".into(),
-                input_path.to_string(),
-                "--puzzle-file".into(),
-                puzzle_path.to_string(),
-            ],
-            day,
-            year,
-        );
-
         let output = call_aoc_cli(&args)?;
 
         if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
             Ok(output)
         } else {
-            Err(AocCliError::BadExitStatus(output))
+            Err(output.into())
         }
     }
 
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("data/inputs/{}.txt", day_padded)
-    }
+    /// Downloads the files selected by `fetch`, overwriting them.
+    pub fn download(day: u8, year: u16, fetch: Fetch) -> Result<Output, AocCliError> {
+        let input_path = data_path(year, "inputs", day, "txt");
+        let puzzle_path = data_path(year, "puzzles", day, "md");
+        create_dir_all(format!("data/{}/inputs", year)).map_err(AocCliError::IoError)?;
+        create_dir_all(format!("data/{}/puzzles", year)).map_err(AocCliError::IoError)?;
+
+        let mut args = vec!["--overwrite".to_string()];
+        if fetch.input {
+            args.extend(["--input-file".into(), input_path.to_string()]);
+        }
+        if fetch.puzzle {
+            args.extend(["--puzzle-file".into(), puzzle_path.to_string()]);
+        }
+        match (fetch.input, fetch.puzzle) {
+            (true, false) => args.push("--input-only".into()),
+            (false, true) => args.push("--puzzle-only".into()),
+            _ => {}
+        }
+        let args = build_args("download", &args, day, Some(year));
+
+        let output = call_aoc_cli(&args)?;
 
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("data/puzzles/{}.md", day_padded)
+        if output.status.success() {
+            println!("---");
+            if fetch.input {
+                println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+            }
+            if fetch.puzzle {
+                println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+            }
+            Ok(output)
+        } else {
+            Err(output.into())
+        }
     }
 
     fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
@@ -233,16 +409,77 @@ pub mod aoc_cli {
         cmd_args
     }
 
+    /// Calls aoc-cli with its output shown as it runs. Stderr is captured for error reporting.
     fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
+        let program = program();
         if cfg!(debug_assertions) {
-            println!("Calling >aoc with: {}", args.join(" "));
+            println!("Calling >{} with: {}", program, args.join(" "));
         }
 
-        Command::new("aoc")
+        Command::new(program)
             .args(args)
             .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
+            .stderr(Stdio::piped())
             .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
+            .map_err(spawn_error)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn bad_exit(stderr: &str) -> AocCliError {
+            AocCliError::BadExitStatus {
+                code: Some(2),
+                stderr: stderr.to_string(),
+            }
+        }
+
+        #[test]
+        fn test_build_args() {
+            assert_eq!(
+                build_args("read", &[], 1, Some(2022)),
+                vec!["--year", "2022", "--day", "1", "read"]
+            );
+            assert_eq!(
+                build_args("download", &["--overwrite".into()], 25, None),
+                vec!["--overwrite", "--day", "25", "download"]
+            );
+        }
+
+        #[test]
+        fn test_hint() {
+            assert!(bad_exit("Error: Puzzle 25 of 2022 is still locked")
+                .hint()
+                .unwrap()
+                .contains("not unlocked"));
+            assert_eq!(
+                bad_exit("Error: Invalid session cookie").hint(),
+                Some(SESSION_HINT)
+            );
+            assert_eq!(
+                bad_exit("Error: HTTP request error: HTTP status client error (400 Bad Request) for url (https://adventofcode.com/2022/day/1/input)").hint(),
+                Some(SESSION_HINT)
+            );
+            assert_eq!(
+                bad_exit("Error: HTTP request error: HTTP status client error (404 Not Found) for url (https://adventofcode.com/2022/day/25)").hint(),
+                Some(LOCKED_HINT)
+            );
+            // numbers elsewhere in the message are not status codes.
+            assert_eq!(
+                bad_exit("Error: could not write 500 bytes of day 404").hint(),
+                None
+            );
+            assert_eq!(bad_exit("something else").hint(), None);
+            assert!(!bad_exit("Error: Invalid session cookie").is_retryable());
+        }
+
+        #[test]
+        fn test_display() {
+            assert_eq!(
+                bad_exit("Error: Puzzle 25 of 2022 is still locked\n").to_string(),
+                "aoc-cli exited with status 2.\nError: Puzzle 25 of 2022 is still locked\nThe puzzle is not unlocked yet. Puzzles unlock at midnight EST (05:00 UTC)."
+            );
+        }
     }
 }
@@ -2,50 +2,637 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::answers::Verdict;
+use advent_of_code::baseline::{self, Baseline, DayTimings, TotalDelta};
+use advent_of_code::bench::{self, BenchConfig};
+use advent_of_code::client::Client;
+use advent_of_code::output::{self, Format};
+use advent_of_code::runner::{self, Outcome, Status, MISSING_INPUT_EXIT_CODE, RUN_DAY_FLAG};
+use advent_of_code::solution::{self, Day, DaySelection};
+use advent_of_code::submit::{self, History};
 use advent_of_code::{ANSI_BOLD, ANSI_RESET};
-use std::process::Command;
+use std::{
+    env, io,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
 
-fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
-            }
+// Solutions are registered by `cargo scaffold`, which inserts new entries above the markers.
+// Their tests already run with the day binaries, so the runner's own test build leaves them out.
+#[path = "bin/2022-01.rs"]
+#[allow(dead_code)]
+#[cfg(not(test))]
+mod y2022_day01;
+#[path = "bin/2022-02.rs"]
+#[allow(dead_code)]
+#[cfg(not(test))]
+mod y2022_day02;
+#[path = "bin/2022-03.rs"]
+#[allow(dead_code)]
+#[cfg(not(test))]
+mod y2022_day03;
+#[path = "bin/2022-04.rs"]
+#[allow(dead_code)]
+#[cfg(not(test))]
+mod y2022_day04;
+#[path = "bin/2022-06.rs"]
+#[allow(dead_code)]
+#[cfg(not(test))]
+mod y2022_day06;
+#[path = "bin/2022-19.rs"]
+#[allow(dead_code)]
+#[cfg(not(test))]
+mod y2022_day19;
+// scaffold:modules
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
+#[cfg(not(test))]
+fn registry() -> Vec<Day> {
+    vec![
+        Day::new::<y2022_day01::Puzzle>(2022, 1),
+        Day::new::<y2022_day02::Puzzle>(2022, 2),
+        Day::new::<y2022_day03::Puzzle>(2022, 3),
+        Day::new::<y2022_day04::Puzzle>(2022, 4),
+        Day::new::<y2022_day06::Puzzle>(2022, 6),
+        Day::new::<y2022_day19::Puzzle>(2022, 19),
+        // scaffold:registry
+    ]
+}
 
-            println!("+--------------------------------------+");
-            println!(
-                "|                {}Day {}{}                |",
-                ANSI_BOLD, day, ANSI_RESET
-            );
-            println!("+--------------------------------------+");
+#[cfg(test)]
+fn registry() -> Vec<Day> {
+    Vec::new()
+}
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
+struct Args {
+    year: Option<u16>,
+    solve: Option<u8>,
+    submit: Option<u8>,
+    format: Format,
+    time: bool,
+    days: Option<DaySelection>,
+    part: Option<u8>,
+    release_only: bool,
+    save_baseline: bool,
+    timeout: Duration,
+    run_day: Option<u8>,
+    profile: Option<String>,
+    all_profiles: bool,
+}
 
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let year = args.opt_value_from_str(["-y", "--year"])?;
+    let solve = args.opt_value_from_str("--solve")?;
+    let format = args.opt_value_from_str("--format")?.unwrap_or(Format::Text);
+    let time = args.contains("--time");
+    let submit = args.opt_value_from_str("--submit")?;
+    let mut part = args.opt_value_from_fn("--part", solution::parse_part)?;
+    if submit.is_some() && part.is_none() {
+        // `cargo submit NN PART` passes the part as a free argument.
+        part = Some(args.free_from_fn(solution::parse_part)?);
+    }
+    let save_baseline = args.contains("--save-baseline");
+    // flags must be taken before the free days argument, which would swallow them otherwise.
+    let release_only = args.contains("--release-only");
+    let timeout = Duration::from_secs(
+        args.opt_value_from_str("--timeout")?
+            .unwrap_or(DEFAULT_TIMEOUT_SECS),
+    );
+    let run_day = args.opt_value_from_str(RUN_DAY_FLAG)?;
+    let profile = args.opt_value_from_str("--profile")?;
+    // children are given their profile explicitly, as `None` selects the default input.
+    let profile = match run_day {
+        Some(_) => profile,
+        None => advent_of_code::resolve_profile(profile),
+    };
+    let all_profiles = args.contains("--all-profiles");
+    let days = match args.opt_value_from_str("--days")? {
+        Some(days) => Some(days),
+        // `cargo time NN` passes the day as a free argument.
+        None => args.opt_free_from_str()?,
+    };
+    Ok(Args {
+        year,
+        solve,
+        submit,
+        format,
+        time,
+        days,
+        part,
+        release_only,
+        save_baseline,
+        timeout,
+        run_day,
+        profile,
+        all_profiles,
+    })
+}
+
+/// Loads a baseline, ignoring it if it is unreadable or measured with another kind of build.
+fn load_baseline(path: &str) -> Baseline {
+    let baseline = Baseline::load(path).unwrap_or_else(|e| {
+        eprintln!("Ignoring unreadable baseline \"{}\": {}", path, e);
+        Baseline::default()
+    });
+    if baseline.is_comparable() {
+        return baseline;
+    }
+    eprintln!(
+        "Ignoring baseline \"{}\", which was measured with {} build.",
+        path,
+        if baseline.optimized {
+            "an optimized"
+        } else {
+            "an unoptimized"
+        }
+    );
+    Baseline::default()
+}
+
+/// Prints the total, along with its difference to the baseline of the days that have one.
+fn print_total(total: Duration, delta: Option<TotalDelta>) {
+    let delta = match delta {
+        Some(delta) => format!(
+            " ({} vs. baseline of {} day{})",
+            baseline::format_delta(delta.baseline, delta.current),
+            delta.days,
+            if delta.days == 1 { "" } else { "s" }
+        ),
+        None => String::new(),
+    };
+    println!(
+        "{}Total:{} {}{:.2}ms{}{}",
+        ANSI_BOLD,
+        ANSI_RESET,
+        ANSI_RESET,
+        total.as_secs_f64() * 1000_f64,
+        delta,
+        ANSI_RESET
+    );
+}
+
+/// Benchmarks the selected days and compares them against the baseline. The baseline is only
+/// written when asked to, so a single slow run does not become the new reference.
+fn run_benchmarks(
+    registry: &[Day],
+    year: u16,
+    days: &DaySelection,
+    profile: Option<&str>,
+    save_baseline: bool,
+) {
+    let config = BenchConfig::default();
+    let path = baseline::baseline_path(year, profile);
+    let mut baseline = load_baseline(&path);
+
+    let mut total = Duration::ZERO;
+    let mut delta: Option<TotalDelta> = None;
+    let mut recorded = false;
+
+    for solution in registry.iter().filter(|s| days.contains(s.day)) {
+        println!("{}Day {:02}{}", ANSI_BOLD, solution.day, ANSI_RESET);
+        match advent_of_code::try_read_input(year, solution.day, profile) {
+            Ok(input) => {
+                let report = solution.bench(&input, &config);
+                let previous = baseline.get(solution.day).copied();
+                bench::print_bench_report(&report, previous.as_ref());
+
+                let current = DayTimings::from(&report);
+                total += current.total();
+                if let Some(previous) = previous {
+                    delta
+                        .get_or_insert_with(TotalDelta::default)
+                        .add(current.total(), previous.total());
                 }
+                // days without a baseline get one right away, existing ones only on request.
+                if save_baseline || previous.is_none() {
+                    baseline.record(solution.day, &report);
+                    recorded = true;
+                }
+            }
+            Err(e) => println!("Could not read input file: {}", e),
+        }
+    }
+
+    print_total(total, delta);
+
+    if recorded {
+        if let Err(e) = baseline.save(&path) {
+            eprintln!("Failed to write baseline \"{}\": {}", path, e);
+            process::exit(1);
+        }
+        println!("Saved baseline to \"{}\".", path);
+    }
+    if delta.is_some() && !save_baseline {
+        println!("Pass --save-baseline to make these timings the new baseline.");
+    }
+}
+
+/// Runs a single day in-process and prints its report for the parent runner to pick up.
+fn run_day(registry: &[Day], year: u16, day: u8, part: Option<u8>, profile: Option<&str>) {
+    let solution = match registry.iter().find(|s| s.day == day) {
+        Some(solution) => solution,
+        None => {
+            eprintln!(
+                "Day {:02} of {} is not registered in src/main.rs.",
+                day, year
             );
+            process::exit(1);
+        }
+    };
+
+    let input = match advent_of_code::try_read_input(year, day, profile) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read input file: {}", e);
+            process::exit(match e.kind() {
+                io::ErrorKind::NotFound => MISSING_INPUT_EXIT_CODE,
+                _ => 1,
+            });
+        }
+    };
+
+    let report = solution.run(&input, part);
+    println!("{}", serde_json::to_string(&report).unwrap());
+}
+
+fn run_day_isolated(registry: &[Day], exe: &Path, year: u16, day: u8, args: &Args) -> Outcome {
+    if registry.iter().any(|s| s.day == day) {
+        runner::run_isolated(
+            exe,
+            year,
+            day,
+            args.part,
+            args.profile.as_deref(),
+            args.timeout,
+        )
+    } else {
+        Outcome::unsolved()
+    }
+}
+
+/// Computes the answer to one part of a day and submits it.
+fn submit_answer(registry: &[Day], exe: &Path, year: u16, day: u8, args: &Args) {
+    let part = args.part.expect("submit always has a part");
+
+    if !registry.iter().any(|s| s.day == day) {
+        eprintln!(
+            "Day {:02} of {} is not registered in src/main.rs.",
+            day, year
+        );
+        process::exit(1);
+    }
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+    // answers can only be submitted for the session's own input.
+    let outcome = runner::run_isolated(exe, year, day, Some(part), None, args.timeout);
+    print_failure(&outcome, args.timeout);
+    let answer = match outcome
+        .report
+        .as_ref()
+        .and_then(|r| r.parts.iter().find(|p| p.part == part))
+        .and_then(|p| p.answer.clone())
+    {
+        Some(answer) => answer,
+        None => {
+            eprintln!("Part {} of day {:02} has no answer to submit.", part, day);
+            process::exit(1);
+        }
+    };
+
+    let history_path = submit::history_path(year, day);
+    let mut history = match History::load(&history_path) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read history \"{}\": {}", history_path, e);
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = history.check(part, &answer) {
+        eprintln!("Refusing to submit {}: {}", answer, refusal);
+        process::exit(1);
+    }
+
+    let client = match Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to submit: {}", e);
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "Submitting {}{}{} for day {:02}, part {}...",
+        ANSI_BOLD, answer, ANSI_RESET, day, part
+    );
+    match client.submit(year, day, part, &answer) {
+        Ok(submission) => {
+            println!("{}", submission);
+            history.record(part, &answer, &submission);
+            if let Err(e) = history.save(&history_path) {
+                eprintln!("Failed to write history \"{}\": {}", history_path, e);
+                process::exit(1);
+            }
+            if !submission.is_correct() {
+                process::exit(1);
             }
-        })
-        .sum();
+        }
+        Err(e) => {
+            eprintln!("Failed to submit: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// The default input, as `None`, followed by every named profile.
+fn all_profiles(year: u16) -> Vec<Option<String>> {
+    match advent_of_code::profiles(year) {
+        Ok(profiles) => std::iter::once(None)
+            .chain(profiles.into_iter().map(Some))
+            .collect(),
+        Err(e) => {
+            eprintln!("Could not list input profiles: {}", e);
+            process::exit(1);
+        }
+    }
+}
 
+fn print_profile(profile: Option<&str>) {
     println!(
-        "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_RESET, total, ANSI_RESET
+        "{}Profile {}{}",
+        ANSI_BOLD,
+        profile.unwrap_or("(default)"),
+        ANSI_RESET
     );
 }
+
+/// Runs a day against the input of every profile and checks each against its known answers.
+/// Returns whether any run failed or regressed.
+fn solve_all_profiles(exe: &Path, year: u16, day: u8, args: &Args) -> bool {
+    let profiles = all_profiles(year);
+    let profiles: Vec<Option<&str>> = profiles
+        .iter()
+        .map(Option::as_deref)
+        .filter(|p| Path::new(&advent_of_code::input_path(year, day, *p)).exists())
+        .collect();
+    if profiles.is_empty() {
+        eprintln!("No profile has an input for day {:02}.", day);
+        process::exit(1);
+    }
+
+    let mut failed = false;
+    for profile in profiles {
+        print_profile(profile);
+        let outcome = runner::run_isolated(exe, year, day, args.part, profile, args.timeout);
+        failed |= outcome.status.is_failure();
+        print_failure(&outcome, args.timeout);
+        if let Some(report) = &outcome.report {
+            let verdicts = solution::verify_report(year, day, profile, report);
+            failed |= verdicts.contains(&Verdict::Regressed);
+            solution::print_report(report, &verdicts);
+        }
+    }
+    failed
+}
+
+fn current_exe() -> PathBuf {
+    match env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            eprintln!("Could not locate the runner executable: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_failure(outcome: &Outcome, timeout: Duration) {
+    match outcome.status {
+        Status::MissingInput => println!("Missing input."),
+        Status::Panicked => println!("Panicked."),
+        Status::TimedOut => println!("Timed out after {:.2?}.", timeout),
+        Status::Failed => println!("Failed."),
+        Status::Solved | Status::Unsolved => return,
+    }
+    if !outcome.stderr.trim().is_empty() {
+        println!("{}", outcome.stderr.trim_end());
+    }
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let year = match advent_of_code::resolve_year(args.year) {
+        Ok(year) => year,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let registry: Vec<Day> = registry().into_iter().filter(|d| d.year == year).collect();
+
+    if let Some(day) = args.run_day {
+        run_day(&registry, year, day, args.part, args.profile.as_deref());
+        return;
+    }
+
+    if let Some(day) = args.solve {
+        match registry.iter().find(|s| s.day == day) {
+            Some(_) if args.all_profiles => {
+                if solve_all_profiles(&current_exe(), year, day, &args) {
+                    process::exit(1);
+                }
+            }
+            Some(solution) => solution.solve(args.part, args.format, args.profile.as_deref()),
+            None => {
+                eprintln!(
+                    "Day {:02} of {} is not registered in src/main.rs.",
+                    day, year
+                );
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // timings of an unoptimized build are meaningless; `cargo all` always builds with --release.
+    if args.release_only && cfg!(debug_assertions) {
+        eprintln!("Refusing to run an unoptimized build. Try `cargo all --release-only`.");
+        process::exit(1);
+    }
+
+    let days = args.days.clone().unwrap_or_else(DaySelection::all);
+
+    if args.time {
+        // only days picked explicitly need a solution; all days means all registered ones.
+        let unregistered = days.iter().find(|d| !registry.iter().any(|s| s.day == *d));
+        if let Some(day) = unregistered.filter(|_| args.days.is_some()) {
+            eprintln!(
+                "Day {:02} of {} is not registered in src/main.rs.",
+                day, year
+            );
+            process::exit(1);
+        }
+        if args.all_profiles {
+            // every profile keeps a baseline of its own.
+            for profile in all_profiles(year) {
+                print_profile(profile.as_deref());
+                run_benchmarks(
+                    &registry,
+                    year,
+                    &days,
+                    profile.as_deref(),
+                    args.save_baseline,
+                );
+            }
+        } else {
+            run_benchmarks(
+                &registry,
+                year,
+                &days,
+                args.profile.as_deref(),
+                args.save_baseline,
+            );
+        }
+        return;
+    }
+
+    let exe = current_exe();
+
+    if let Some(day) = args.submit {
+        submit_answer(&registry, &exe, year, day, &args);
+        return;
+    }
+
+    let mut failed = false;
+
+    if args.format != Format::Text {
+        let mut records = Vec::new();
+        for day in days.iter() {
+            let outcome = run_day_isolated(&registry, &exe, year, day, &args);
+            if outcome.status == Status::MissingInput {
+                eprintln!("Day {:02}: skipped, as it has no input file.", day);
+            }
+            if outcome.status.is_failure() {
+                failed = true;
+                eprintln!(
+                    "Day {:02}: {:?}\n{}",
+                    day,
+                    outcome.status,
+                    outcome.stderr.trim_end()
+                );
+            }
+            if let Some(report) = &outcome.report {
+                let verdicts = solution::verify_report(year, day, args.profile.as_deref(), report);
+                failed |= verdicts.contains(&Verdict::Regressed);
+                records.extend(output::records(year, day, report, &verdicts));
+            }
+        }
+        output::print_records(args.format, &records);
+        if failed {
+            process::exit(1);
+        }
+        return;
+    }
+
+    // baselines cover both parts, so a single part is not comparable.
+    let baseline = match args.part {
+        None => load_baseline(&baseline::baseline_path(year, args.profile.as_deref())),
+        Some(_) => Baseline::default(),
+    };
+    let mut delta: Option<TotalDelta> = None;
+    let mut total = Duration::ZERO;
+    let mut skipped = 0;
+    let mut missing_input = Vec::new();
+
+    for day in days.iter() {
+        let outcome = run_day_isolated(&registry, &exe, year, day, &args);
+        // days without a solution or input yet would only add noise.
+        match outcome.status {
+            Status::Unsolved => {
+                skipped += 1;
+                continue;
+            }
+            Status::MissingInput => {
+                missing_input.push(format!("{:02}", day));
+                continue;
+            }
+            _ => {}
+        }
+
+        println!("+--------------------------------------+");
+        println!(
+            "|                {}Day {:02}{}                |",
+            ANSI_BOLD, day, ANSI_RESET
+        );
+        println!("+--------------------------------------+");
+
+        failed |= outcome.status.is_failure();
+        print_failure(&outcome, args.timeout);
+
+        let report = match &outcome.report {
+            Some(report) => report,
+            None => continue,
+        };
+
+        let verdicts = solution::verify_report(year, day, args.profile.as_deref(), report);
+        failed |= verdicts.contains(&Verdict::Regressed);
+        solution::print_report(report, &verdicts);
+        total += report.total_elapsed();
+        if let Some(previous) = baseline.get(day) {
+            delta
+                .get_or_insert_with(TotalDelta::default)
+                .add(report.total_elapsed(), previous.total());
+        }
+    }
+
+    if skipped > 0 {
+        println!(
+            "Skipped {} day{} without a solution.",
+            skipped,
+            if skipped == 1 { "" } else { "s" }
+        );
+    }
+    if !missing_input.is_empty() {
+        // downloads only fetch the session's own inputs, a profile's are added by hand.
+        let hint = match args.profile.as_deref() {
+            Some(profile) => {
+                let path = advent_of_code::input_path(year, 1, Some(profile));
+                let folder = Path::new(&path).parent().unwrap_or(Path::new(""));
+                format!(
+                    "Add {} to `{}`.",
+                    if missing_input.len() == 1 {
+                        "it"
+                    } else {
+                        "them"
+                    },
+                    folder.display()
+                )
+            }
+            None => "Try `cargo download NN`.".to_string(),
+        };
+        println!(
+            "Skipped day{} {} without an input file. {}",
+            if missing_input.len() == 1 { "" } else { "s" },
+            missing_input.join(", "),
+            hint
+        );
+    }
+    print_total(total, delta);
+
+    if failed {
+        println!(
+            "Days that do not compile cannot be listed, as they keep `cargo all` from building. Use `cargo check --bin {}-NN` to find them.",
+            year
+        );
+        process::exit(1);
+    }
+}
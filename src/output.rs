@@ -0,0 +1,142 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::answers::Verdict;
+use crate::solution::DayReport;
+use serde::Serialize;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown format \"{}\", expected one of: text, json, csv",
+                s
+            )),
+        }
+    }
+}
+
+/// One row of machine-readable output: the result of a single part of a single day.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: &'static str,
+    pub parse_ns: u128,
+    pub solve_ns: u128,
+}
+
+pub fn records(year: u16, day: u8, report: &DayReport, verdicts: &[Verdict]) -> Vec<Record> {
+    report
+        .parts
+        .iter()
+        .zip(verdicts)
+        .map(|(part, verdict)| Record {
+            year,
+            day,
+            part: part.part,
+            answer: part.answer.clone(),
+            status: verdict.name(),
+            parse_ns: report.parse_elapsed.as_nanos(),
+            solve_ns: part.elapsed.as_nanos(),
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let mut out = String::from("year,day,part,answer,status,parse_ns,solve_ns\n");
+    for r in records {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            r.year,
+            r.day,
+            r.part,
+            csv_field(r.answer.as_deref().unwrap_or("")),
+            r.status,
+            r.parse_ns,
+            r.solve_ns
+        ));
+    }
+    out
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).expect("records are always serializable")
+}
+
+/// Prints records in a machine-readable format. Text output is handled by the callers.
+pub fn print_records(format: Format, records: &[Record]) {
+    match format {
+        Format::Json => println!("{}", to_json(records)),
+        Format::Csv => print!("{}", to_csv(records)),
+        Format::Text => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Option<&str>) -> Record {
+        Record {
+            year: 2022,
+            day: 1,
+            part: 2,
+            answer: answer.map(|a| a.to_string()),
+            status: "unknown",
+            parse_ns: 1500,
+            solve_ns: 42,
+        }
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert_eq!("csv".parse::<Format>(), Ok(Format::Csv));
+        assert_eq!("text".parse::<Format>(), Ok(Format::Text));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&[record(Some("45000")), record(None), record(Some("a,\"b\""))]),
+            "year,day,part,answer,status,parse_ns,solve_ns\n2022,1,2,45000,unknown,1500,42\n2022,1,2,,unknown,1500,42\n2022,1,2,\"a,\"\"b\"\"\",unknown,1500,42\n"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&[record(None)])).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "year": 2022, "day": 1, "part": 2, "answer": null, "status": "unknown", "parse_ns": 1500, "solve_ns": 42 }
+            ])
+        );
+    }
+}
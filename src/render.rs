@@ -0,0 +1,333 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{ANSI_BOLD, ANSI_RESET};
+use std::{iter::Peekable, str::Lines};
+
+/// Width used when the terminal size cannot be determined, e.g. when piping.
+pub const DEFAULT_WIDTH: usize = 80;
+
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+pub fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as usize)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Returns the puzzle from the "Part Two" heading onwards, if part two is unlocked.
+pub fn part_two(markdown: &str) -> Option<&str> {
+    let mut offset = 0;
+    for line in markdown.split_inclusive('\n') {
+        if line.contains(PART_TWO_HEADING) {
+            return Some(&markdown[offset..]);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// A line of a puzzle file, or a whole fenced code block.
+pub enum Block<'a> {
+    Line(&'a str),
+    Code(Vec<&'a str>),
+}
+
+/// Iterates over the lines of a puzzle file, collecting fenced code blocks into one item.
+pub struct Blocks<'a> {
+    lines: Peekable<Lines<'a>>,
+}
+
+pub fn blocks(markdown: &str) -> Blocks<'_> {
+    Blocks {
+        lines: markdown.lines().peekable(),
+    }
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Block<'a>> {
+        let line = self.lines.next()?;
+        if !line.trim_end().starts_with("```") {
+            return Some(Block::Line(line));
+        }
+        let mut code: Vec<&str> = self
+            .lines
+            .by_ref()
+            .take_while(|l| !l.trim_end().starts_with("```"))
+            .collect();
+        // the converted puzzles end every code block with an empty line.
+        if code.last().is_some_and(|l| l.is_empty()) {
+            code.pop();
+        }
+        Some(Block::Code(code))
+    }
+}
+
+/// Position of the delimiter closing the one at `start`, skipping escaped and nested ones.
+fn closing(chars: &[char], start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Replaces `[text](target)` links with their text. Brackets and parentheses are matched by
+/// depth, so texts can contain links and targets can contain parentheses. Brackets that do not
+/// form a link are kept as they are.
+fn strip_links(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                out.extend(&chars[i..i + 2]);
+                i += 2;
+                continue;
+            }
+            '`' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&c| c == '`')
+                    .map_or(chars.len(), |p| i + p + 2);
+                out.extend(&chars[i..end]);
+                i = end;
+                continue;
+            }
+            '[' => {
+                let link = closing(&chars, i, '[', ']')
+                    .filter(|&text_end| chars.get(text_end + 1) == Some(&'('))
+                    .and_then(|text_end| {
+                        closing(&chars, text_end + 1, '(', ')').map(|end| (text_end, end))
+                    });
+                if let Some((text_end, end)) = link {
+                    let text: String = chars[i + 1..text_end].iter().collect();
+                    out.push_str(&strip_links(&text));
+                    i = end + 1;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+    out
+}
+
+/// A word as printed, along with its width without escape codes.
+struct Word {
+    text: String,
+    width: usize,
+}
+
+/// Splits a line of inline markdown into words, turning `*emphasis*` into bold and dropping
+/// backticks, link targets and escapes.
+fn words(line: &str) -> Vec<Word> {
+    let line = strip_links(line);
+    let mut words = Vec::new();
+    let mut word = Word {
+        text: String::new(),
+        width: 0,
+    };
+    let mut bold = false;
+    let mut chars = line.chars().peekable();
+
+    let mut flush = |word: &mut Word, bold: bool| {
+        if word.width > 0 {
+            if bold {
+                word.text.push_str(ANSI_RESET);
+            }
+            words.push(std::mem::replace(
+                word,
+                Word {
+                    text: if bold {
+                        ANSI_BOLD.into()
+                    } else {
+                        String::new()
+                    },
+                    width: 0,
+                },
+            ));
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek().is_some_and(|c| c.is_ascii_punctuation()) => {
+                word.text.push(chars.next().unwrap());
+                word.width += 1;
+            }
+            '`' => {}
+            '*' => {
+                bold = !bold;
+                word.text
+                    .push_str(if bold { ANSI_BOLD } else { ANSI_RESET });
+            }
+            c if c.is_whitespace() => flush(&mut word, bold),
+            c => {
+                word.text.push(c);
+                word.width += 1;
+            }
+        }
+    }
+    flush(&mut word, bold);
+    words
+}
+
+/// Wraps inline markdown to `width`, prefixing the first line with `first` and the rest with
+/// `rest`, which must be as wide as `first`.
+fn wrap(text: &str, width: usize, first: &str, rest: &str) -> Vec<String> {
+    let indent = first.chars().count();
+    let mut lines = Vec::new();
+    let mut line = first.to_string();
+    let mut line_width = indent;
+
+    for word in words(text) {
+        if line_width > indent && line_width + 1 + word.width > width {
+            lines.push(std::mem::replace(&mut line, rest.to_string()));
+            line_width = indent;
+        }
+        if line_width > indent {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(&word.text);
+        line_width += word.width;
+    }
+    lines.push(line);
+    lines
+}
+
+fn code_box(code: &[&str]) -> Vec<String> {
+    let width = code.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let mut lines = vec![format!("┌{}┐", "─".repeat(width + 2))];
+    for line in code {
+        let padding = width - line.chars().count();
+        lines.push(format!("│ {}{} │", line, " ".repeat(padding)));
+    }
+    lines.push(format!("└{}┘", "─".repeat(width + 2)));
+    lines
+}
+
+/// Renders a puzzle file for the terminal.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut blocks = blocks(markdown).peekable();
+
+    while let Some(block) = blocks.next() {
+        let line = match block {
+            Block::Code(code) => {
+                out.extend(code_box(&code));
+                continue;
+            }
+            Block::Line(line) => line,
+        };
+        let trimmed = line.trim_end();
+
+        if matches!(
+            blocks.peek(),
+            Some(Block::Line(next)) if !next.is_empty() && next.chars().all(|c| c == '-')
+        ) {
+            blocks.next();
+            let heading = trimmed.trim_start_matches('\\');
+            out.push(format!("{}{}{}", ANSI_BOLD, heading, ANSI_RESET));
+        } else if let Some(item) = trimmed.strip_prefix("* ") {
+            out.extend(wrap(item, width, "  • ", "    "));
+        } else if trimmed.is_empty() {
+            out.push(String::new());
+        } else {
+            out.extend(wrap(trimmed, width, "", ""));
+        }
+    }
+
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(s: &str) -> String {
+        s.replace(ANSI_BOLD, "").replace(ANSI_RESET, "")
+    }
+
+    #[test]
+    fn test_render_inline() {
+        assert_eq!(
+            render("a total of `*6000*` [Calories](/2022). \\*\\*", 80),
+            format!("a total of {}6000{} Calories. **", ANSI_BOLD, ANSI_RESET)
+        );
+        assert_eq!(
+            render("*two words*", 80),
+            format!(
+                "{}two{} {}words{}",
+                ANSI_BOLD, ANSI_RESET, ANSI_BOLD, ANSI_RESET
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_links() {
+        assert_eq!(
+            render("You can also [Shareon [Twitter](https://twitter.com/intent/tweet?text=I%27ve+completed+%22Calorie+Counting%22+%2D+Day+1+%2D+Advent+of+Code+2022&url=https%3A%2F%2Fadventofcode%2Ecom%2F2022%2Fday%2F1&related=ericwastl&hashtags=AdventOfCode) [Mastodon](javascript:void(0);)] this puzzle.", 80),
+            "You can also [Shareon Twitter Mastodon] this puzzle."
+        );
+        assert_eq!(
+            render(
+                "an [unmatched bracket, a [link](/a) and a `[code](span)`",
+                80
+            ),
+            "an [unmatched bracket, a link and a [code](span)"
+        );
+    }
+
+    #[test]
+    fn test_render_blocks() {
+        let markdown = "\\--- Day 1: Calorie Counting ---\n----------\n\nFor example:\n\n```\n1000\n20\n\n```\n\n* one two three four\n";
+        assert_eq!(
+            strip(&render(markdown, 12)),
+            "--- Day 1: Calorie Counting ---\n\nFor example:\n\n┌──────┐\n│ 1000 │\n│ 20   │\n└──────┘\n\n  • one two\n    three\n    four"
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("aaa bbb ccc", 7, "", ""),
+            vec!["aaa bbb".to_string(), "ccc".to_string()]
+        );
+        // words longer than the width get a line of their own.
+        assert_eq!(
+            wrap("a bbbbbbbbbb c", 5, "", ""),
+            vec!["a".to_string(), "bbbbbbbbbb".to_string(), "c".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_part_two() {
+        let markdown = "Part one.\n\n\\--- Part Two ---\n----------\n\nPart two.\n";
+        assert_eq!(
+            part_two(markdown),
+            Some("\\--- Part Two ---\n----------\n\nPart two.\n")
+        );
+        assert_eq!(part_two("Part one.\n"), None);
+    }
+}
@@ -0,0 +1,235 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::solution::DayReport;
+use std::{
+    io::{self, Read},
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Hidden runner flag that makes the runner execute a single day and print its report as JSON.
+pub const RUN_DAY_FLAG: &str = "--run-day";
+
+/// Exit code of a Rust process that panicked.
+const PANIC_EXIT_CODE: i32 = 101;
+
+/// Exit code of a `--run-day` child that found no input file for its day.
+pub const MISSING_INPUT_EXIT_CODE: i32 = 3;
+
+/// How running a day ended. There is no build error status: days are compiled into the runner,
+/// so a day that does not compile stops the runner from building in the first place.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The day has no input file, which is expected before it has been downloaded.
+    MissingInput,
+    Panicked,
+    TimedOut,
+    Failed,
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Panicked | Status::TimedOut | Status::Failed)
+    }
+}
+
+/// The result of running a day in a child process.
+#[derive(Debug)]
+pub struct Outcome {
+    pub status: Status,
+    pub report: Option<DayReport>,
+    pub stderr: String,
+}
+
+impl Outcome {
+    pub fn unsolved() -> Self {
+        Self {
+            status: Status::Unsolved,
+            report: None,
+            stderr: String::new(),
+        }
+    }
+}
+
+fn read_to_string_in_background(
+    mut reader: impl Read + Send + 'static,
+) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        // a failed read leaves whatever was captured so far.
+        let _ = reader.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Classifies a finished child process. The report is the last line of stdout, so that solutions
+/// can still print debug output.
+pub fn classify(exit: Option<ExitStatus>, stdout: &str, stderr: String) -> Outcome {
+    let failed = |status| Outcome {
+        status,
+        report: None,
+        stderr: stderr.clone(),
+    };
+
+    let exit = match exit {
+        Some(exit) => exit,
+        None => return failed(Status::TimedOut),
+    };
+
+    if !exit.success() {
+        // no exit code means the process was killed by a signal, e.g. on stack overflow.
+        return match exit.code() {
+            Some(PANIC_EXIT_CODE) | None => failed(Status::Panicked),
+            Some(MISSING_INPUT_EXIT_CODE) => failed(Status::MissingInput),
+            Some(_) => failed(Status::Failed),
+        };
+    }
+
+    let report = stdout
+        .lines()
+        .last()
+        .and_then(|line| serde_json::from_str::<DayReport>(line).ok());
+
+    match report {
+        Some(report) => {
+            let status = if report.parts.iter().any(|p| p.answer.is_some()) {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            };
+            Outcome {
+                status,
+                report: Some(report),
+                stderr,
+            }
+        }
+        None => failed(Status::Failed),
+    }
+}
+
+/// Runs a registered day by re-invoking the runner executable with `--run-day`, killing it when
+/// it exceeds `timeout`.
+pub fn run_isolated(
+    exe: &Path,
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+    profile: Option<&str>,
+    timeout: Duration,
+) -> Outcome {
+    let mut cmd = Command::new(exe);
+    cmd.args([RUN_DAY_FLAG, &day.to_string(), "--year", &year.to_string()]);
+    if let Some(part) = part {
+        cmd.args(["--part", &part.to_string()]);
+    }
+    if let Some(profile) = profile {
+        cmd.args(["--profile", profile]);
+    }
+
+    let mut child = match cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            return Outcome {
+                status: Status::Failed,
+                report: None,
+                stderr: format!("could not spawn runner: {}", e),
+            }
+        }
+    };
+
+    let stdout = read_to_string_in_background(child.stdout.take().unwrap());
+    let stderr = read_to_string_in_background(child.stderr.take().unwrap());
+
+    let exit = match wait_with_timeout(&mut child, timeout) {
+        Ok(exit) => exit,
+        Err(e) => {
+            return Outcome {
+                status: Status::Failed,
+                report: None,
+                stderr: format!("could not wait for runner: {}", e),
+            }
+        }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    classify(exit, &stdout, stderr)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    fn exit(code: i32) -> Option<ExitStatus> {
+        Some(ExitStatus::from_raw(code << 8))
+    }
+
+    const REPORT: &str = r#"{"parse_elapsed":{"secs":0,"nanos":5},"parts":[{"part":1,"answer":"42","elapsed":{"secs":0,"nanos":7}}]}"#;
+
+    #[test]
+    fn test_classify() {
+        let outcome = classify(
+            exit(0),
+            &format!("debug output\n{}\n", REPORT),
+            String::new(),
+        );
+        assert_eq!(outcome.status, Status::Solved);
+        assert_eq!(
+            outcome.report.unwrap().parts[0].answer.as_deref(),
+            Some("42")
+        );
+
+        let unsolved = REPORT.replace(r#""42""#, "null");
+        assert_eq!(
+            classify(exit(0), &unsolved, String::new()).status,
+            Status::Unsolved
+        );
+
+        let outcome = classify(exit(101), "", "thread 'main' panicked".into());
+        assert_eq!(outcome.status, Status::Panicked);
+        assert_eq!(outcome.stderr, "thread 'main' panicked");
+
+        assert_eq!(
+            classify(Some(ExitStatus::from_raw(6)), "", String::new()).status,
+            Status::Panicked
+        );
+        assert_eq!(
+            classify(exit(MISSING_INPUT_EXIT_CODE), "", String::new()).status,
+            Status::MissingInput
+        );
+        assert!(!Status::MissingInput.is_failure());
+        assert_eq!(classify(exit(1), "", String::new()).status, Status::Failed);
+        assert_eq!(
+            classify(exit(0), "garbage", String::new()).status,
+            Status::Failed
+        );
+        assert_eq!(classify(None, "", String::new()).status, Status::TimedOut);
+    }
+}
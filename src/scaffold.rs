@@ -0,0 +1,351 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::examples::{self, Filled};
+use crate::template::{self, Context};
+use crate::{data_path, launch, solution_path};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+};
+
+/// The runner whose registry scaffolded days are added to.
+pub const MAIN_PATH: &str = "src/main.rs";
+
+const MODULES_MARKER: &str = "// scaffold:modules";
+const REGISTRY_MARKER: &str = "        // scaffold:registry";
+
+/// The lines `src/main.rs` declares and registers a day's module with.
+fn registration(year: u16, day: u8) -> (String, String) {
+    let module = format!("y{}_day{:02}", year, day);
+    (
+        format!(
+            "#[path = \"bin/{}-{:02}.rs\"]\n#[allow(dead_code)]\n#[cfg(not(test))]\nmod {};\n",
+            year, day, module
+        ),
+        format!(
+            "        Day::new::<{}::Puzzle>({}, {}),\n",
+            module, year, day
+        ),
+    )
+}
+
+/// Adds a day's module to the source of the runner. Returns `None` if it is registered already.
+pub fn register(main: &str, year: u16, day: u8) -> Option<String> {
+    if main.contains(&format!("mod y{}_day{:02};", year, day)) {
+        return None;
+    }
+    let (module, entry) = registration(year, day);
+    Some(
+        main.replacen(MODULES_MARKER, &format!("{}{}", module, MODULES_MARKER), 1)
+            .replacen(REGISTRY_MARKER, &format!("{}{}", entry, REGISTRY_MARKER), 1),
+    )
+}
+
+/// Removes a day's module from the source of the runner, if it is registered as scaffolded.
+pub fn unregister(main: &str, year: u16, day: u8) -> Option<String> {
+    let (module, entry) = registration(year, day);
+    if !main.contains(&module) {
+        return None;
+    }
+    Some(main.replacen(&module, "", 1).replacen(&entry, "", 1))
+}
+
+/// Adds the day's module to the registry in `src/main.rs` so `cargo all` picks it up.
+/// Returns whether it was added, i.e. `false` if it was registered already.
+pub fn register_module(year: u16, day: u8) -> io::Result<bool> {
+    let main = fs::read_to_string(MAIN_PATH)?;
+    match register(&main, year, day) {
+        Some(main) => fs::write(MAIN_PATH, main).map(|_| true),
+        None => Ok(false),
+    }
+}
+
+/// Removes the day's module from the registry in `src/main.rs`. Returns whether it was removed.
+pub fn unregister_module(year: u16, day: u8) -> io::Result<bool> {
+    let main = fs::read_to_string(MAIN_PATH)?;
+    match unregister(&main, year, day) {
+        Some(main) => fs::write(MAIN_PATH, main).map(|_| true),
+        None => Ok(false),
+    }
+}
+
+fn read_puzzle(year: u16, day: u8) -> Option<String> {
+    fs::read_to_string(data_path(year, "puzzles", day, "md")).ok()
+}
+
+/// Picks the expected example answers, preferring the ones passed on the command line over the
+/// ones guessed from a downloaded puzzle.
+pub fn expected_answers(
+    puzzle: Option<&str>,
+    overrides: [Option<String>; 2],
+) -> [Option<String>; 2] {
+    let guesses = puzzle.map(examples::expected_answers).unwrap_or_default();
+
+    let [one, two] = overrides;
+    let [guess_one, guess_two] = guesses;
+    [one.or(guess_one), two.or(guess_two)]
+}
+
+/// A solution rendered from a template, with the example answers that were filled in.
+pub struct Module {
+    pub source: String,
+    pub answers: [Option<String>; 2],
+    /// The parts whose expected answers were filled into the tests.
+    pub filled: Vec<u8>,
+    /// The parts whose expected answers do not fit the answer type.
+    pub skipped: Vec<u8>,
+}
+
+/// Renders a day's solution from a template, using the downloaded puzzle for its title and
+/// expected example answers if there is one.
+pub fn render_module(
+    template: &str,
+    year: u16,
+    day: u8,
+    answer_type: &str,
+    overrides: [Option<String>; 2],
+) -> Module {
+    let puzzle = read_puzzle(year, day);
+    let context = Context {
+        year,
+        day,
+        title: puzzle.as_deref().and_then(template::puzzle_title),
+        answer_type: answer_type.to_string(),
+    };
+    let source = template::render(template, &context);
+    let answers = expected_answers(puzzle.as_deref(), overrides);
+    let filled = examples::fill_expected_answers(&source, &answers);
+    Module {
+        source: filled.source,
+        answers,
+        filled: filled.parts,
+        skipped: filled.skipped,
+    }
+}
+
+/// Loads a template like [`template::load`], naming its path in the error.
+pub fn load_template(name: &str) -> io::Result<String> {
+    template::load(name).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "Failed to read template \"{}\": {}",
+                template::template_path(name),
+                e
+            ),
+        )
+    })
+}
+
+/// Creates a day's solution from the named template and tells the user about it. An existing
+/// solution is only replaced when `overwrite` is set. Errors name the file they are about.
+pub fn create_module(
+    year: u16,
+    day: u8,
+    template_name: &str,
+    answer_type: &str,
+    overrides: [Option<String>; 2],
+    overwrite: bool,
+) -> io::Result<Module> {
+    let template = load_template(template_name)?;
+    let module = render_module(&template, year, day, answer_type, overrides);
+
+    let path = solution_path(year, day);
+    OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(overwrite)
+        .create_new(!overwrite)
+        .open(&path)
+        .and_then(|mut file| file.write_all(module.source.as_bytes()))
+        .map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed to create module file \"{}\": {}", path, e),
+            )
+        })?;
+    println!("Created module file \"{}\"", path);
+    Ok(module)
+}
+
+/// Registers a day's module in the runner and adds its debug configurations, telling the user
+/// about both. Debug configurations are optional, so failing to add them is only reported.
+pub fn register_day(year: u16, day: u8) -> io::Result<()> {
+    if register_module(year, day)? {
+        println!("Registered module in \"{}\"", MAIN_PATH);
+    }
+
+    match launch::add_day(year, day) {
+        Ok(added) if !added.is_empty() => {
+            println!("Added debug configurations to \"{}\"", launch::LAUNCH_PATH);
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Could not update \"{}\": {}", launch::LAUNCH_PATH, e),
+    }
+    Ok(())
+}
+
+/// Names of the templates in the project's `templates` folder, along with the default template.
+fn template_names() -> io::Result<Vec<String>> {
+    let mut names = vec![template::DEFAULT_TEMPLATE.to_string()];
+    let entries = match fs::read_dir(template::TEMPLATE_DIR) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(names),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if let (Some(stem), Some("rs")) =
+            (path.file_stem(), path.extension().and_then(|e| e.to_str()))
+        {
+            let stem = stem.to_string_lossy().into_owned();
+            if !names.contains(&stem) {
+                names.push(stem);
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Whether a solution is exactly as scaffolded from one of the templates, with or without the
+/// guessed example answers filled in, i.e. nobody has started working on it. Days scaffolded
+/// before their puzzle was downloaded are rendered without a title, so both are checked.
+pub fn is_untouched(year: u16, day: u8, source: &str) -> io::Result<bool> {
+    let puzzle = read_puzzle(year, day);
+    let answers = expected_answers(puzzle.as_deref(), Default::default());
+    let mut titles = vec![None];
+    if let Some(title) = puzzle.as_deref().and_then(template::puzzle_title) {
+        titles.push(Some(title));
+    }
+
+    for name in template_names()? {
+        let template = template::load(&name)?;
+        for answer_type in template::ANSWER_TYPES {
+            for title in &titles {
+                let context = Context {
+                    year,
+                    day,
+                    title: title.clone(),
+                    answer_type: answer_type.to_string(),
+                };
+                let rendered = template::render(&template, &context);
+                if source == rendered
+                    || source == examples::fill_expected_answers(&rendered, &answers).source
+                {
+                    return Ok(true);
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Fills the example answers guessed from the downloaded puzzle into an existing solution's
+/// untouched tests. Returns the guesses along with what was filled in.
+pub fn update_expected_answers(year: u16, day: u8) -> io::Result<([Option<String>; 2], Filled)> {
+    let path = solution_path(year, day);
+    let (source, puzzle) = match (fs::read_to_string(&path), read_puzzle(year, day)) {
+        (Ok(source), Some(puzzle)) => (source, puzzle),
+        _ => return Ok((Default::default(), Filled::default())),
+    };
+
+    let answers = examples::expected_answers(&puzzle);
+    let filled = examples::fill_expected_answers(&source, &answers);
+    if !filled.parts.is_empty() {
+        fs::write(&path, &filled.source)?;
+    }
+    Ok((answers, filled))
+}
+
+/// Prints the example answers that were filled into the tests of a solution, and the ones that
+/// were skipped.
+pub fn print_expected_answers(
+    path: &str,
+    answers: &[Option<String>; 2],
+    filled: &[u8],
+    skipped: &[u8],
+) {
+    for part in filled {
+        println!(
+            "🎄 Expecting {} for part {} of the example in \"{}\". Review it before relying on it.",
+            answers[*part as usize - 1].as_deref().unwrap_or_default(),
+            part,
+            path
+        );
+    }
+    for part in skipped {
+        println!(
+            "🎄 Not expecting {} for part {} of the example in \"{}\", as it does not fit the answer type.",
+            answers[*part as usize - 1].as_deref().unwrap_or_default(),
+            part,
+            path
+        );
+    }
+}
+
+/// Fills the guessed example answers into an existing solution like [`update_expected_answers`]
+/// and tells the user what was filled in.
+pub fn fill_expected_answers(year: u16, day: u8) {
+    let path = solution_path(year, day);
+    match update_expected_answers(year, day) {
+        Ok((answers, filled)) => {
+            print_expected_answers(&path, &answers, &filled.parts, &filled.skipped)
+        }
+        Err(e) => eprintln!("Could not update \"{}\": {}", path, e),
+    }
+}
+
+/// The hint printed once a day is ready, which leaves out `--year` for the default year.
+pub fn solve_hint(year: u16, day: u8) -> String {
+    let year_arg = if crate::default_year() == Some(year) {
+        String::new()
+    } else {
+        format!(" --year {}", year)
+    };
+    format!(
+        "🎄 Type `cargo solve {:02}{}` to run your solution.",
+        day, year_arg
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: &str = "mod y2022_day01;\n// scaffold:modules\n\nfn registry() -> Vec<Day> {\n    vec![\n        // scaffold:registry\n    ]\n}\n";
+
+    #[test]
+    fn test_register() {
+        let registered = register(MAIN, 2022, 5).unwrap();
+        assert!(registered.contains(
+            "#[path = \"bin/2022-05.rs\"]\n#[allow(dead_code)]\n#[cfg(not(test))]\nmod y2022_day05;\n// scaffold:modules"
+        ));
+        assert!(registered.contains(
+            "        Day::new::<y2022_day05::Puzzle>(2022, 5),\n        // scaffold:registry"
+        ));
+        assert_eq!(register(&registered, 2022, 5), None);
+
+        assert_eq!(unregister(&registered, 2022, 5).as_deref(), Some(MAIN));
+        // modules that were not added by scaffold are left alone.
+        assert_eq!(unregister(MAIN, 2022, 1), None);
+    }
+
+    #[test]
+    fn test_is_untouched() {
+        let template = template::load(template::DEFAULT_TEMPLATE).unwrap();
+        // scaffolded before the puzzle was downloaded.
+        let untitled = template::render(
+            &template,
+            &Context {
+                year: 2022,
+                day: 1,
+                title: None,
+                answer_type: "u64".into(),
+            },
+        );
+        assert!(is_untouched(2022, 1, &untitled).unwrap());
+        assert!(!is_untouched(2022, 1, &format!("{}// wip\n", untitled)).unwrap());
+    }
+}
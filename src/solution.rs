@@ -0,0 +1,280 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::answers::{self, Verdict};
+use crate::bench::{self, BenchConfig, BenchReport};
+use crate::output::{self, Format};
+use crate::{try_read_input, ANSI_BOLD, ANSI_RESET};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// A day's solution, split into a parse stage and the two puzzle parts.
+///
+/// Every `src/bin/<year>-NN.rs` implements this for a `Puzzle` type, which lets the day binary and
+/// the `cargo all` runner share the same code path.
+pub trait Solution {
+    type Parsed<'a>;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Self::Parsed<'_>;
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne>;
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo>;
+}
+
+/// A type-erased entry in the day registry.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    run: fn(&str, Option<u8>) -> DayReport,
+    bench: fn(&str, &BenchConfig) -> BenchReport,
+}
+
+impl Day {
+    pub fn new<S: Solution>(year: u16, day: u8) -> Self {
+        Self {
+            year,
+            day,
+            run: run::<S>,
+            bench: bench::bench::<S>,
+        }
+    }
+
+    /// Runs the solution, restricted to a single part if `part` is set.
+    pub fn run(&self, input: &str, part: Option<u8>) -> DayReport {
+        (self.run)(input, part)
+    }
+
+    pub fn bench(&self, input: &str, config: &BenchConfig) -> BenchReport {
+        (self.bench)(input, config)
+    }
+
+    /// Reads the day's input, runs the solution and prints the result in the given format.
+    pub fn solve(&self, part: Option<u8>, format: Format, profile: Option<&str>) {
+        let input = match try_read_input(self.year, self.day, profile) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Could not read input file: {}", e);
+                process::exit(1);
+            }
+        };
+        let report = self.run(&input, part);
+        let verdicts = verify_report(self.year, self.day, profile, &report);
+
+        match format {
+            Format::Text => print_report(&report, &verdicts),
+            format => output::print_records(
+                format,
+                &output::records(self.year, self.day, &report, &verdicts),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayReport {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    pub fn total_elapsed(&self) -> Duration {
+        self.parse_elapsed + self.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    }
+}
+
+fn timed<T>(func: impl FnOnce() -> T) -> (T, Duration) {
+    let timer = Instant::now();
+    let result = func();
+    (result, timer.elapsed())
+}
+
+fn run_part<T: Display>(part: u8, func: impl FnOnce() -> Option<T>) -> PartReport {
+    let (answer, elapsed) = timed(func);
+    PartReport {
+        part,
+        answer: answer.map(|a| a.to_string()),
+        elapsed,
+    }
+}
+
+pub fn run<S: Solution>(input: &str, part: Option<u8>) -> DayReport {
+    let (parsed, parse_elapsed) = timed(|| S::parse(input));
+    let mut parts = Vec::new();
+    if part.is_none_or(|p| p == 1) {
+        parts.push(run_part(1, || S::part_one(&parsed)));
+    }
+    if part.is_none_or(|p| p == 2) {
+        parts.push(run_part(2, || S::part_two(&parsed)));
+    }
+    DayReport {
+        parse_elapsed,
+        parts,
+    }
+}
+
+/// A set of days given as a comma-separated list of days and ranges, e.g. `1-6,19`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DaySelection(BTreeSet<u8>);
+
+impl DaySelection {
+    pub fn all() -> Self {
+        Self((1..=25).collect())
+    }
+
+    pub fn contains(&self, day: u8) -> bool {
+        self.0.contains(&day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("\"{}\" is not a day between 1 and 25", s.trim())),
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = BTreeSet::new();
+        for item in s.split(',').filter(|item| !item.trim().is_empty()) {
+            match item.split_once('-') {
+                Some((from, to)) => {
+                    let (from, to) = (parse_day(from)?, parse_day(to)?);
+                    if from > to {
+                        return Err(format!("\"{}\" is not an ascending range", item));
+                    }
+                    days.extend(from..=to);
+                }
+                None => {
+                    days.insert(parse_day(item)?);
+                }
+            }
+        }
+        if days.is_empty() {
+            return Err("no days selected".into());
+        }
+        Ok(Self(days))
+    }
+}
+
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("\"{}\" is not a part, expected 1 or 2", s)),
+    }
+}
+
+/// Checks a report against the day's known answers, treating an unreadable answer store as
+/// having no known answers.
+pub fn verify_report(
+    year: u16,
+    day: u8,
+    profile: Option<&str>,
+    report: &DayReport,
+) -> Vec<Verdict> {
+    let known = answers::known_answers(year, day, profile).unwrap_or_else(|e| {
+        eprintln!("Could not read known answers: {}", e);
+        Default::default()
+    });
+    answers::check(report, &known)
+}
+
+pub fn print_report(report: &DayReport, verdicts: &[Verdict]) {
+    println!(
+        "🎄 {}Parser{}: ✓ (elapsed: {:.2?})",
+        ANSI_BOLD, ANSI_RESET, report.parse_elapsed
+    );
+    for (part, verdict) in report.parts.iter().zip(verdicts) {
+        print!(
+            "🎄 {}Part {}{}: {} ",
+            ANSI_BOLD,
+            part.part,
+            ANSI_RESET,
+            verdict.symbol()
+        );
+        match &part.answer {
+            Some(answer) => println!("{} (elapsed: {:.2?})", answer, part.elapsed),
+            None => println!("not solved."),
+        }
+    }
+}
+
+struct Args {
+    format: Format,
+    part: Option<u8>,
+    profile: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        format: args.opt_value_from_str("--format")?.unwrap_or(Format::Text),
+        part: args.opt_value_from_fn("--part", parse_part)?,
+        profile: args.opt_value_from_str("--profile")?,
+    })
+}
+
+/// Entry point for a day binary: reads the day's input, runs the solution and prints the result.
+pub fn solve_day<S: Solution>(year: u16, day: u8) {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let profile = crate::resolve_profile(args.profile);
+    Day::new::<S>(year, day).solve(args.part, args.format, profile.as_deref());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day_selection() {
+        let selection: DaySelection = "1-6,19".parse().unwrap();
+        assert_eq!(selection.iter().collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6, 19]);
+        assert!(selection.contains(19));
+        assert!(!selection.contains(7));
+
+        let selection: DaySelection = "3, 3,2-3".parse().unwrap();
+        assert_eq!(selection.iter().collect::<Vec<_>>(), [2, 3]);
+
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("6-1".parse::<DaySelection>().is_err());
+        assert!("".parse::<DaySelection>().is_err());
+        assert!("a-b".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!(parse_part("1"), Ok(1));
+        assert_eq!(parse_part("2"), Ok(2));
+        assert!(parse_part("3").is_err());
+    }
+}
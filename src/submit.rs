@@ -0,0 +1,308 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{data_path, load_json, save_json};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Path of the submission history of a day, next to its puzzle file.
+pub fn history_path(year: u16, day: u8) -> String {
+    data_path(year, "puzzles", day, "history.json")
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Submission {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited(Duration),
+    AlreadySolved,
+    Unrecognized(String),
+}
+
+impl Submission {
+    pub fn is_correct(&self) -> bool {
+        *self == Submission::Correct
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Submission::Correct => "correct",
+            Submission::Incorrect => "incorrect",
+            Submission::TooHigh => "too_high",
+            Submission::TooLow => "too_low",
+            Submission::RateLimited(_) => "rate_limited",
+            Submission::AlreadySolved => "already_solved",
+            Submission::Unrecognized(_) => "unrecognized",
+        }
+    }
+}
+
+/// Formats a wait time the way the server does, e.g. `1m 3s`.
+fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{}s", s),
+        (m, 0) => format!("{}m", m),
+        (m, s) => format!("{}m {}s", m, s),
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Submission::Correct => write!(f, "That's the right answer!"),
+            Submission::Incorrect => write!(f, "That's not the right answer."),
+            Submission::TooHigh => write!(f, "That's not the right answer; it is too high."),
+            Submission::TooLow => write!(f, "That's not the right answer; it is too low."),
+            Submission::RateLimited(wait) => write!(
+                f,
+                "You gave an answer too recently. Wait {} before trying again.",
+                format_wait(*wait)
+            ),
+            Submission::AlreadySolved => {
+                write!(f, "This part is either already solved or not unlocked yet.")
+            }
+            Submission::Unrecognized(text) => write!(f, "Unrecognized response: {}", text),
+        }
+    }
+}
+
+/// Returns the text of the `<article>` that holds the response message, without markup.
+fn response_text(html: &str) -> String {
+    let article = html
+        .find("<article")
+        .and_then(|start| {
+            let end = html[start..].find("</article>")?;
+            Some(&html[start..start + end])
+        })
+        .unwrap_or(html);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses a wait time such as "You have 1m 3s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = text[start..].find(" left to wait")? + start;
+
+    let mut secs = 0;
+    for token in text[start..end].split_whitespace() {
+        let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Classifies the response page of an answer submission.
+pub fn parse_response(html: &str) -> Submission {
+    let text = response_text(html);
+
+    if text.contains("That's the right answer") {
+        Submission::Correct
+    } else if text.contains("answer too recently") {
+        match parse_wait(&text) {
+            Some(wait) => Submission::RateLimited(wait),
+            None => Submission::Unrecognized(text),
+        }
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Submission::TooHigh
+        } else if text.contains("your answer is too low") {
+            Submission::TooLow
+        } else {
+            Submission::Incorrect
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Submission::AlreadySolved
+    } else {
+        Submission::Unrecognized(text)
+    }
+}
+
+/// A submitted answer and the server's verdict on it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub part: u8,
+    pub answer: String,
+    pub result: String,
+    pub submitted_at: u64,
+}
+
+/// Why an answer is not worth submitting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyCorrect,
+    KnownWrong,
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect => write!(f, "it was already accepted."),
+            Refusal::KnownWrong => write!(f, "it was already rejected."),
+            Refusal::NotBelow(bound) => {
+                write!(f, "{} was already too high.", bound)
+            }
+            Refusal::NotAbove(bound) => {
+                write!(f, "{} was already too low.", bound)
+            }
+        }
+    }
+}
+
+/// Every answer submitted for a day, as persisted in `data/<year>/puzzles/NN.history.json`.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub submissions: Vec<Entry>,
+}
+
+impl History {
+    /// Loads the history from `path`. A missing file yields an empty history.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(load_json(path)?.unwrap_or_default())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save_json(path, self)
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, submission: &Submission) {
+        let submitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.submissions.push(Entry {
+            part,
+            answer: answer.to_string(),
+            result: submission.name().to_string(),
+            submitted_at,
+        });
+    }
+
+    /// Checks an answer against earlier verdicts for the same part. Numeric answers must also lie
+    /// strictly between the highest "too low" and the lowest "too high" answer.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        let entries = self.submissions.iter().filter(|e| e.part == part);
+        let value = answer.parse::<i128>().ok();
+
+        for entry in entries {
+            let bound = entry.answer.parse::<i128>().ok();
+            match entry.result.as_str() {
+                "correct" if entry.answer == answer => return Err(Refusal::AlreadyCorrect),
+                "incorrect" | "too_high" | "too_low" if entry.answer == answer => {
+                    return Err(Refusal::KnownWrong)
+                }
+                "too_high" if matches!((value, bound), (Some(v), Some(b)) if v > b) => {
+                    return Err(Refusal::NotBelow(entry.answer.clone()))
+                }
+                "too_low" if matches!((value, bound), (Some(v), Some(b)) if v < b) => {
+                    return Err(Refusal::NotAbove(entry.answer.clone()))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response(&page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit. <a href=\"/2022/day/1#part2\">[Continue to Part Two]</a>")),
+            Submission::Correct
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>")),
+            Submission::TooHigh
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too low.  please wait one minute before trying again.")),
+            Submission::TooLow
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data.")),
+            Submission::Incorrect
+        );
+        assert_eq!(
+            parse_response(&page("You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2022/day/1\">[Return to Day 1]</a>")),
+            Submission::AlreadySolved
+        );
+        assert_eq!(
+            parse_response(&page("Something <em>else</em>.")),
+            Submission::Unrecognized("Something else.".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(
+            parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a>")),
+            Submission::RateLimited(Duration::from_secs(34))
+        );
+        assert_eq!(
+            parse_wait("You have 1m 3s left to wait."),
+            Some(Duration::from_secs(63))
+        );
+        assert_eq!(format_wait(Duration::from_secs(63)), "1m 3s");
+        assert_eq!(format_wait(Duration::from_secs(120)), "2m");
+    }
+
+    #[test]
+    fn test_history_check() {
+        let mut history = History::default();
+        history.record(1, "100", &Submission::TooHigh);
+        history.record(1, "10", &Submission::TooLow);
+        history.record(1, "50", &Submission::Incorrect);
+        history.record(2, "abc", &Submission::Correct);
+
+        assert_eq!(history.check(1, "42"), Ok(()));
+        assert_eq!(history.check(1, "50"), Err(Refusal::KnownWrong));
+        assert_eq!(history.check(1, "100"), Err(Refusal::KnownWrong));
+        assert_eq!(
+            history.check(1, "101"),
+            Err(Refusal::NotBelow("100".into()))
+        );
+        assert_eq!(history.check(1, "9"), Err(Refusal::NotAbove("10".into())));
+        assert_eq!(history.check(1, "abc"), Ok(()));
+        assert_eq!(history.check(2, "abc"), Err(Refusal::AlreadyCorrect));
+        assert_eq!(history.check(2, "101"), Ok(()));
+    }
+}
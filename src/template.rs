@@ -0,0 +1,123 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{fs, io};
+
+/// Folder with the templates `cargo scaffold` can create a solution from.
+pub const TEMPLATE_DIR: &str = "templates";
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// The answer types a template can be rendered with, the first being the default.
+pub const ANSWER_TYPES: [&str; 3] = ["u64", "i64", "String"];
+
+pub fn parse_answer_type(s: &str) -> Result<String, String> {
+    match ANSWER_TYPES.contains(&s) {
+        true => Ok(s.to_string()),
+        false => Err(format!(
+            "\"{}\" is not an answer type, expected one of {}",
+            s,
+            ANSWER_TYPES.join(", ")
+        )),
+    }
+}
+
+/// Used when the project has no `templates/default.rs` of its own.
+const BUILTIN_DEFAULT: &str = include_str!("../templates/default.rs");
+
+/// Path of a named template, e.g. `templates/grid.rs`.
+pub fn template_path(name: &str) -> String {
+    format!("{}/{}.rs", TEMPLATE_DIR, name)
+}
+
+/// Loads a template from the project, falling back to the built-in one for the default template.
+pub fn load(name: &str) -> io::Result<String> {
+    match fs::read_to_string(template_path(name)) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound && name == DEFAULT_TEMPLATE => {
+            Ok(BUILTIN_DEFAULT.to_string())
+        }
+        result => result,
+    }
+}
+
+/// Extracts the title from the `--- Day 1: Calorie Counting ---` heading of a puzzle file.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let heading = line.trim().trim_start_matches('\\');
+        let rest = heading.strip_prefix("--- Day ")?.strip_suffix(" ---")?;
+        let (_, title) = rest.split_once(": ")?;
+        Some(title.trim().to_string())
+    })
+}
+
+/// The values a template's placeholders are replaced with.
+pub struct Context {
+    pub year: u16,
+    pub day: u8,
+    pub title: Option<String>,
+    pub answer_type: String,
+}
+
+impl Context {
+    fn values(&self) -> [(&'static str, String); 5] {
+        [
+            ("year", self.year.to_string()),
+            ("day", self.day.to_string()),
+            ("day_padded", format!("{:02}", self.day)),
+            (
+                "title",
+                self.title.clone().unwrap_or_else(|| "Untitled".into()),
+            ),
+            ("answer_type", self.answer_type.clone()),
+        ]
+    }
+}
+
+/// Replaces the `{{year}}`, `{{day}}`, `{{day_padded}}`, `{{title}}` and `{{answer_type}}`
+/// placeholders. Anything else, including other text in double braces, is left as is.
+pub fn render(template: &str, context: &Context) -> String {
+    context
+        .values()
+        .iter()
+        .fold(template.to_string(), |source, (name, value)| {
+            source.replace(&format!("{{{{{}}}}}", name), value)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let context = Context {
+            year: 2022,
+            day: 7,
+            title: Some("No Space Left On Device".into()),
+            answer_type: "u64".into(),
+        };
+        assert_eq!(
+            render(
+                "// Day {{day}}: {{title}}\nconst DAYS: u8 = {{ day_padded }};\nsolve({{year}}, {{day}}, \"data/{{day_padded}}\", \"{{}}\");",
+                &context
+            ),
+            "// Day 7: No Space Left On Device\nconst DAYS: u8 = {{ day_padded }};\nsolve(2022, 7, \"data/07\", \"{{}}\");"
+        );
+    }
+
+    #[test]
+    fn test_parse_answer_type() {
+        assert_eq!(parse_answer_type("String"), Ok("String".to_string()));
+        assert!(parse_answer_type("u32").is_err());
+    }
+
+    #[test]
+    fn test_puzzle_title() {
+        assert_eq!(
+            puzzle_title("\\--- Day 1: Calorie Counting ---\n----------\n"),
+            Some("Calorie Counting".to_string())
+        );
+        assert_eq!(puzzle_title("no heading"), None);
+    }
+}
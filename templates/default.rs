@@ -0,0 +1,59 @@
+// Day {{day}}: {{title}}
+use advent_of_code::Solution;
+
+fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+#[allow(unused_variables)]
+pub fn part_one(lines: &[&str]) -> Option<{{answer_type}}> {
+    None
+}
+
+#[allow(unused_variables)]
+pub fn part_two(lines: &[&str]) -> Option<{{answer_type}}> {
+    None
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Vec<&'a str>;
+    type PartOne = {{answer_type}};
+    type PartTwo = {{answer_type}};
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne> {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo> {
+        part_two(parsed)
+    }
+}
+
+fn main() {
+    advent_of_code::solve_day::<Puzzle>({{year}}, {{day}});
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file_for_year({{year}}, "examples", {{day}});
+        let lines = parse(&input);
+        assert_eq!(part_one(&lines), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file_for_year({{year}}, "examples", {{day}});
+        let lines = parse(&input);
+        assert_eq!(part_two(&lines), None);
+    }
+}
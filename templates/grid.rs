@@ -0,0 +1,59 @@
+// Day {{day}}: {{title}}
+use advent_of_code::Solution;
+
+type Grid = Vec<Vec<u8>>;
+
+fn parse(input: &str) -> Grid {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+#[allow(unused_variables)]
+pub fn part_one(grid: &[Vec<u8>]) -> Option<{{answer_type}}> {
+    None
+}
+
+#[allow(unused_variables)]
+pub fn part_two(grid: &[Vec<u8>]) -> Option<{{answer_type}}> {
+    None
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed<'a> = Grid;
+    type PartOne = {{answer_type}};
+    type PartTwo = {{answer_type}};
+
+    fn parse(input: &str) -> Self::Parsed<'_> {
+        parse(input)
+    }
+
+    fn part_one(parsed: &Self::Parsed<'_>) -> Option<Self::PartOne> {
+        part_one(parsed)
+    }
+
+    fn part_two(parsed: &Self::Parsed<'_>) -> Option<Self::PartTwo> {
+        part_two(parsed)
+    }
+}
+
+fn main() {
+    advent_of_code::solve_day::<Puzzle>({{year}}, {{day}});
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = parse(&advent_of_code::read_file_for_year({{year}}, "examples", {{day}}));
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = parse(&advent_of_code::read_file_for_year({{year}}, "examples", {{day}}));
+        assert_eq!(part_two(&input), None);
+    }
+}
@@ -0,0 +1,301 @@
+//! Runs the `download`, `read` and `start` commands against a scripted fake `aoc` executable.
+#![cfg(unix)]
+
+use std::{
+    env, fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Logs its arguments and writes the files it is asked for. `FAKE_AOC_FAIL` makes it print the
+/// given message to stderr and fail, `FAKE_AOC_SIGNAL` makes it kill itself.
+const FAKE_AOC: &str = r#"#!/bin/sh
+printf '%s\n' "$*" >> "$(dirname "$0")/calls.log"
+if [ "$1" = "-V" ]; then
+    echo "aoc-cli 0.12.0"
+    exit 0
+fi
+if [ -n "$FAKE_AOC_FAIL" ]; then
+    echo "$FAKE_AOC_FAIL" >&2
+    exit 2
+fi
+if [ -n "$FAKE_AOC_SIGNAL" ]; then
+    kill -9 $$
+fi
+while [ $# -gt 0 ]; do
+    case "$1" in
+        --input-file) shift; printf '1000\n2000\n' > "$1" ;;
+        --puzzle-file) shift; printf 'For example:\n\n```\n1000\n\n```\n' > "$1" ;;
+        read) echo "the puzzle" ;;
+    esac
+    shift
+done
+"#;
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A scratch project directory with a fake `aoc` next to it.
+struct Sandbox {
+    root: PathBuf,
+}
+
+impl Sandbox {
+    fn new() -> Self {
+        let root = env::temp_dir().join(format!(
+            "aoc-cli-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        fs::create_dir_all(root.join("project")).unwrap();
+
+        let fake = root.join("aoc");
+        fs::write(&fake, FAKE_AOC).unwrap();
+        fs::set_permissions(&fake, fs::Permissions::from_mode(0o755)).unwrap();
+
+        Self { root }
+    }
+
+    fn project(&self) -> PathBuf {
+        self.root.join("project")
+    }
+
+    fn fake(&self) -> PathBuf {
+        self.root.join("aoc")
+    }
+
+    fn calls(&self) -> Vec<String> {
+        fs::read_to_string(self.root.join("calls.log"))
+            .unwrap_or_default()
+            .lines()
+            .map(|l| l.to_string())
+            .collect()
+    }
+
+    fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.project().join(path)).unwrap()
+    }
+
+    fn run(&self, bin: &str, program: &Path, args: &[&str], env: &[(&str, &str)]) -> Output {
+        Command::new(bin)
+            .args(args)
+            .arg("--aoc-cli")
+            .current_dir(self.project())
+            .env("AOC_CLI", program)
+            .envs(env.iter().copied())
+            .output()
+            .unwrap()
+    }
+
+    fn download(&self, args: &[&str], env: &[(&str, &str)]) -> Output {
+        self.run(env!("CARGO_BIN_EXE_download"), &self.fake(), args, env)
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn test_download_places_files() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.download(&["1", "--year", "2022"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert_eq!(sandbox.read("data/2022/inputs/01.txt"), "1000\n2000\n");
+    assert_eq!(
+        sandbox.read("data/2022/puzzles/01.md"),
+        "For example:\n\n```\n1000\n\n```\n"
+    );
+    assert_eq!(sandbox.read("data/2022/examples/01.txt"), "1000\n");
+}
+
+#[test]
+fn test_download_argument_order() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.download(&["7", "--year", "2021"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    assert_eq!(
+        sandbox.calls(),
+        vec![
+            "-V".to_string(),
+            "--overwrite --input-file data/2021/inputs/07.txt --puzzle-file data/2021/puzzles/07.md --year 2021 --day 7 download".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_download_keeps_existing_files() {
+    let sandbox = Sandbox::new();
+    fs::create_dir_all(sandbox.project().join("data/2022/inputs")).unwrap();
+    fs::write(sandbox.project().join("data/2022/inputs/01.txt"), "mine").unwrap();
+
+    // only the puzzle is missing.
+    let output = sandbox.download(&["1", "--year", "2022"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(sandbox.read("data/2022/inputs/01.txt"), "mine");
+    assert_eq!(
+        sandbox.calls()[1],
+        "--overwrite --puzzle-file data/2022/puzzles/01.md --puzzle-only --year 2022 --day 1 download"
+    );
+
+    // with part two unlocked but unsolved, its answer is still missing.
+    fs::write(
+        sandbox.project().join("data/2022/puzzles/01.md"),
+        "notes\n\nYour puzzle answer was `1`.\n\n\\--- Part Two ---\n",
+    )
+    .unwrap();
+    let output = sandbox.download(&["1", "--year", "2022"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(sandbox.calls().len(), 4);
+    assert_eq!(sandbox.calls()[3], sandbox.calls()[1]);
+
+    // with part two solved, there is nothing left to fetch.
+    fs::write(
+        sandbox.project().join("data/2022/puzzles/01.md"),
+        "notes\n\nYour puzzle answer was `1`.\n\n\\--- Part Two ---\n\nYour puzzle answer was `2`.\n",
+    )
+    .unwrap();
+    let output = sandbox.download(&["1", "--year", "2022"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("already downloaded"));
+    assert_eq!(sandbox.calls().len(), 5);
+}
+
+#[test]
+fn test_download_force_overwrites() {
+    let sandbox = Sandbox::new();
+    fs::create_dir_all(sandbox.project().join("data/2022/inputs")).unwrap();
+    fs::write(sandbox.project().join("data/2022/inputs/01.txt"), "stale").unwrap();
+
+    let output = sandbox.download(&["1", "--year", "2022", "--force"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(sandbox.read("data/2022/inputs/01.txt"), "1000\n2000\n");
+}
+
+#[test]
+fn test_start_resumes() {
+    let sandbox = Sandbox::new();
+    fs::create_dir_all(sandbox.project().join("src/bin")).unwrap();
+    fs::write(
+        sandbox.project().join("src/main.rs"),
+        "// scaffold:modules\nfn registry() {\n        // scaffold:registry\n}\n",
+    )
+    .unwrap();
+    let start = |sandbox: &Sandbox| {
+        sandbox.run(
+            env!("CARGO_BIN_EXE_start"),
+            &sandbox.fake(),
+            &["1", "--year", "2022"],
+            &[],
+        )
+    };
+
+    let output = start(&sandbox);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let module = sandbox.read("src/bin/2022-01.rs");
+    assert!(module.contains("solve_day::<Puzzle>(2022, 1)"));
+    assert_eq!(sandbox.read("data/2022/examples/01.txt"), "1000\n");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("For example:"));
+
+    // a second run keeps everything from the first one.
+    fs::write(sandbox.project().join("src/bin/2022-01.rs"), "// mine").unwrap();
+    let output = start(&sandbox);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(sandbox.read("src/bin/2022-01.rs"), "// mine");
+    assert_eq!(
+        sandbox
+            .read("src/main.rs")
+            .matches("mod y2022_day01;")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_read_argument_order() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.run(
+        env!("CARGO_BIN_EXE_read"),
+        &sandbox.fake(),
+        &["3", "--year", "2022"],
+        &[],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("the puzzle"));
+    assert_eq!(sandbox.calls()[1], "--year 2022 --day 3 read");
+}
+
+#[test]
+fn test_command_not_found() {
+    let sandbox = Sandbox::new();
+    let missing = sandbox.root.join("missing");
+    let output = sandbox.run(
+        env!("CARGO_BIN_EXE_download"),
+        &missing,
+        &["1", "--year", "2022"],
+        &[],
+    );
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("aoc-cli is not present in environment"));
+    assert!(stderr(&output).contains("cargo install aoc-cli"));
+}
+
+#[test]
+fn test_command_not_callable() {
+    let sandbox = Sandbox::new();
+    fs::set_permissions(sandbox.fake(), fs::Permissions::from_mode(0o644)).unwrap();
+    let output = sandbox.download(&["1", "--year", "2022"], &[]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("aoc-cli could not be called"));
+}
+
+#[test]
+fn test_bad_exit_status() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.download(
+        &["25", "--year", "2022"],
+        &[("FAKE_AOC_FAIL", "Error: Puzzle 25 of 2022 is still locked")],
+    );
+    assert!(!output.status.success());
+    let stderr = stderr(&output);
+    assert!(stderr.contains("aoc-cli exited with status 2."));
+    assert!(stderr.contains("Error: Puzzle 25 of 2022 is still locked"));
+    assert!(stderr.contains("The puzzle is not unlocked yet."));
+
+    let output = sandbox.download(
+        &["1", "--year", "2022"],
+        &[(
+            "FAKE_AOC_FAIL",
+            "Error: HTTP request error: HTTP status client error (400 Bad Request) for url (https://adventofcode.com/2022/day/1/input)",
+        )],
+    );
+    assert!(self::stderr(&output).contains("The session cookie is missing or expired."));
+}
+
+#[test]
+fn test_killed_by_signal() {
+    let sandbox = Sandbox::new();
+    let output = sandbox.download(&["1", "--year", "2022"], &[("FAKE_AOC_SIGNAL", "1")]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("aoc-cli was terminated by a signal."));
+}
+
+#[test]
+fn test_io_error() {
+    let sandbox = Sandbox::new();
+    // a file where the data folder should be.
+    fs::write(sandbox.project().join("data"), "").unwrap();
+    let output = sandbox.download(&["1", "--year", "2022"], &[]);
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("could not write output files to file system"));
+    assert_eq!(sandbox.calls(), vec!["-V".to_string()]);
+}
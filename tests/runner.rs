@@ -0,0 +1,65 @@
+//! Runs the `cargo all` runner binary with its command-line flags.
+
+use std::{
+    env, fs,
+    process::{self, Command, Output},
+};
+
+/// Runs the runner in an empty project, where every day lacks an input.
+fn run(name: &str, args: &[&str]) -> Output {
+    let project = env::temp_dir().join(format!("aoc-runner-{}-{}", process::id(), name));
+    fs::create_dir_all(&project).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_advent_of_code"))
+        .args(args)
+        .args(["--year", "2022"])
+        .current_dir(&project)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&project).unwrap();
+    output
+}
+
+fn check_release_only(output: Output) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("failed to parse"), "{}", stderr);
+    if cfg!(debug_assertions) {
+        assert!(!output.status.success());
+        assert!(stderr.contains("Refusing to run an unoptimized build."));
+    } else {
+        assert!(output.status.success(), "{}", stderr);
+    }
+}
+
+#[test]
+fn test_release_only() {
+    check_release_only(run("all", &["--release-only"]));
+}
+
+#[test]
+fn test_release_only_with_days() {
+    check_release_only(run(
+        "days",
+        &["--days", "1-6,19", "--part", "2", "--release-only"],
+    ));
+}
+
+#[test]
+fn test_missing_input_hint() {
+    let output = run("download-hint", &["--days", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Skipped day 01 without an input file. Try `cargo download NN`."),
+        "{}",
+        stdout
+    );
+
+    // a profile's inputs are not downloaded, so the hint names their folder instead.
+    let output = run("profile-hint", &["--days", "1", "--profile", "alice"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout
+            .contains("Skipped day 01 without an input file. Add it to `data/2022/inputs/alice`."),
+        "{}",
+        stdout
+    );
+}